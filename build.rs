@@ -301,6 +301,7 @@ fn generate_bindings(include_dir: Option<String>) {
         .header(header.to_string_lossy())
         .allowlist_function("AbstractState_.*")
         .allowlist_function("PropsSI")
+        .allowlist_function("PropsSImulti")
         .allowlist_function("Props1SI")
         .allowlist_function("HAPropsSI")
         .allowlist_function("PhaseSI")
@@ -308,14 +309,20 @@ fn generate_bindings(include_dir: Option<String>) {
         .allowlist_function("get_fluid_param_string_len")
         .allowlist_function("get_input_pair_index")
         .allowlist_function("get_param_index")
+        .allowlist_function("get_parameter_information_string")
         .allowlist_function("get_global_param_string")
         .allowlist_function("get_config_bool")
         .allowlist_function("get_config_double")
         .allowlist_function("get_config_string")
+        .allowlist_function("get_config_as_json_string")
         .allowlist_function("set_reference_stateS")
         .allowlist_function("set_config_string")
         .allowlist_function("set_config_double")
         .allowlist_function("set_config_bool")
+        .allowlist_function("set_debug_level")
+        .allowlist_function("get_debug_level")
+        .allowlist_function("add_fluids_as_JSON")
+        .allowlist_function("set_departure_functions")
         .generate()
         .expect("bindgen generation failed");
 
@@ -335,6 +342,16 @@ fn generate_bindings(include_dir: Option<String>) {
         "get_config_string",
         "coolprop_has_get_config_string",
     );
+    emit_symbol_cfg(
+        &bindings_src,
+        "get_config_as_json_string",
+        "coolprop_has_get_config_as_json_string",
+    );
+    emit_symbol_cfg(
+        &bindings_src,
+        "AbstractState_get_binary_interaction_double",
+        "coolprop_has_abstractstate_get_binary_interaction_double",
+    );
     emit_symbol_cfg(
         &bindings_src,
         "AbstractState_set_mass_fractions",
@@ -346,6 +363,18 @@ fn generate_bindings(include_dir: Option<String>) {
         "coolprop_has_abstractstate_get_mass_fractions",
     );
 
+    emit_symbol_cfg(&bindings_src, "PropsSImulti", "coolprop_has_propssimulti");
+    emit_symbol_cfg(
+        &bindings_src,
+        "add_fluids_as_JSON",
+        "coolprop_has_add_fluids_as_json",
+    );
+    emit_symbol_cfg(
+        &bindings_src,
+        "set_departure_functions",
+        "coolprop_has_set_departure_functions",
+    );
+
     let out_path = PathBuf::from(env::var("OUT_DIR").unwrap()).join("bindings.rs");
     fs::write(&out_path, bindings_src)
         .expect("failed to write bindgen output");
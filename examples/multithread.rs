@@ -6,14 +6,9 @@ fn main() -> Result<()> {
     println!("*** Multithreaded CoolProp demo ***");
     println!("Spawning workers that independently query thermodynamic properties.");
 
-    // Warm up the dynamic loader on the main thread so the shared library is fully initialized
-    // before the workers start. This avoids redundant fluid-library loads under concurrency.
-    {
-        let _ = props_si("P", "T", 300.0, "Q", 0.0, "Water")?;
-        let mut warm_state = AbstractState::new("HEOS", "Water")?;
-        warm_state.update(InputPair::PT, 101_325.0, 300.0)?;
-        let _ = warm_state.get(Param::Hmass)?;
-    }
+    // The first `AbstractState` loads CoolProp's fluid database exactly once, serialized across
+    // threads. Doing it here, before the workers start, keeps their start-up latency uniform.
+    AbstractState::new("HEOS", "Water")?;
 
     let mut handles = Vec::new();
     for idx in 0..4 {
@@ -1,18 +1,45 @@
 use crate::{
-    Error, Result,
+    BufferGrowth, Error, Fill, Result, grow_buffer,
     indices::{Indices, InputPair, Param, Phase, global_indices},
 };
 use std::{
     cell::Cell,
+    collections::HashMap,
     ffi::CString,
     fmt,
     marker::PhantomData,
     os::raw::{c_char, c_long},
     ptr,
+    sync::{Mutex, OnceLock, PoisonError},
+    time::{Duration, Instant},
 };
 
 const ERR_BUF_LEN: usize = 1024;
 const DEFAULT_STR_BUF_LEN: usize = 1024;
+// Multiples of `rel_jitter` applied to (v1, v2) by `update_with_perturbation`, in retry order.
+const PERTURBATION_STEPS: [(f64, f64); 8] = [
+    (1.0, 1.0),
+    (-1.0, -1.0),
+    (1.0, -1.0),
+    (-1.0, 1.0),
+    (2.0, 2.0),
+    (-2.0, -2.0),
+    (2.0, -2.0),
+    (-2.0, 2.0),
+];
+// Temperature grid size and bisection tolerance (K) used by `inversion_temperature`.
+const INVERSION_SCAN_POINTS: usize = 64;
+const INVERSION_T_TOL: f64 = 1e-6;
+// Reduced density above which a density input is flagged as a likely molar/mass mix-up;
+// compressed liquids stay below roughly 3.5 times the reducing density.
+#[cfg(any(test, all(feature = "log", debug_assertions)))]
+const MAX_REDUCED_DENSITY: f64 = 5.0;
+// Reduced density above which a value reads as a liquid; saturated liquids sit at roughly 2 to
+// 3.5 times the reducing density.
+#[cfg(any(test, all(feature = "log", debug_assertions)))]
+const MIN_LIQUID_REDUCED_DENSITY: f64 = 2.0;
+// Liquid mole fraction below which `k_values` treats `y / x` as undefined.
+const MIN_LIQUID_FRACTION: f64 = 1e-12;
 
 /// High-level handle to CoolProp's `AbstractState`.
 ///
@@ -39,12 +66,21 @@ const DEFAULT_STR_BUF_LEN: usize = 1024;
 pub struct AbstractState {
     indices: &'static Indices,
     handle: c_long,
+    // Inputs of the most recent `update` call, kept for `Debug` output and error context.
+    last_update: Option<(InputPair, f64, f64)>,
+    // Whether any update has succeeded, so state-dependent `get` calls can fail early.
+    updated: bool,
+    // First buffer size tried by `fluid_param_string`; see `set_string_capacity`.
+    string_capacity: usize,
+    // Model parameters set through the mutators, in call order, replayed by `model_clone`.
+    model_overrides: Vec<ModelOverride>,
     // CoolProp state objects are not safe to share across threads concurrently.
     // This keeps `Send` while preventing `Sync`.
     _not_sync: PhantomData<Cell<()>>,
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Outputs returned by [`AbstractState::update_and_common_out`].
 pub struct BatchCommonOutputs {
     /// Temperature at each sampled input state, in kelvin.
@@ -60,7 +96,13 @@ pub struct BatchCommonOutputs {
 }
 
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Full phase-envelope data extracted from CoolProp.
+///
+/// With the `serde` feature this and the other result structs ([`SpinodalCurve`],
+/// [`CriticalPoint`], [`BatchCommonOutputs`]) can be serialized, for example to cache expensive
+/// mixture computations. Field names are the serialized keys and `x`/`y` are nested arrays, one
+/// row per component.
 pub struct PhaseEnvelope {
     /// Saturation temperature coordinates, in kelvin.
     pub temperature: Vec<f64>,
@@ -76,7 +118,88 @@ pub struct PhaseEnvelope {
     pub y: Vec<Vec<f64>>,
 }
 
+impl PhaseEnvelope {
+    /// Bubble- and dew-point temperatures (K) at pressure `p` (Pa), as `(t_bubble, t_dew)`.
+    ///
+    /// CoolProp traces the envelope as a single path from the low-pressure dew side, around the
+    /// critical point, and down the bubble side. The feed composition is read from the vapor
+    /// composition of the first point; at every point, the phase carrying the feed is the bulk
+    /// phase, and the point lies on the dew branch when that phase is the less dense one and on
+    /// the bubble branch otherwise. Each branch is then interpolated linearly in `ln(p)` over the
+    /// first segment that brackets `p`, so in a retrograde region the crossing nearest the
+    /// low-pressure end of the branch is used.
+    ///
+    /// Returns `None` if `p` is not positive or either branch does not reach `p`, for example
+    /// above the cricondenbar.
+    pub fn at_pressure(&self, p: f64) -> Option<(f64, f64)> {
+        if p.is_nan() || p <= 0.0 {
+            return None;
+        }
+        let feed: Vec<f64> = self
+            .y
+            .iter()
+            .map(|component| component.first().copied())
+            .collect::<Option<_>>()?;
+        Some((
+            self.branch_temperature_at(&feed, false, p)?,
+            self.branch_temperature_at(&feed, true, p)?,
+        ))
+    }
+
+    /// Whether `point` lies on the dew branch: the phase carrying the `feed` composition is the
+    /// less dense one there. Composition alone cannot tell the branches apart, because CoolProp
+    /// keeps the feed in `y` along the whole traced path.
+    fn on_dew_branch(&self, feed: &[f64], point: usize) -> bool {
+        const FEED_TOLERANCE: f64 = 1e-6;
+        let carries_feed = |phase: &[Vec<f64>]| {
+            phase.len() == feed.len()
+                && phase
+                    .iter()
+                    .zip(feed)
+                    .all(|(component, z)| (component[point] - z).abs() <= FEED_TOLERANCE)
+        };
+        let (rho_liq, rho_vap) = (self.rhomolar_liq[point], self.rhomolar_vap[point]);
+        // The bulk phase is the vapor unless only the liquid carries the feed composition.
+        if carries_feed(&self.y) || !carries_feed(&self.x) {
+            rho_vap < rho_liq
+        } else {
+            rho_liq < rho_vap
+        }
+    }
+
+    /// Number of points present in every coordinate vector and composition row, so that
+    /// hand-built or deserialized envelopes with short rows are never indexed past their end.
+    fn point_count(&self) -> usize {
+        self.x
+            .iter()
+            .chain(&self.y)
+            .map(Vec::len)
+            .fold(self.temperature.len(), usize::min)
+            .min(self.pressure.len())
+            .min(self.rhomolar_liq.len())
+            .min(self.rhomolar_vap.len())
+    }
+
+    fn branch_temperature_at(&self, feed: &[f64], dew: bool, p: f64) -> Option<f64> {
+        let on_branch = |point: usize| self.on_dew_branch(feed, point) == dew;
+        let points = self.point_count();
+        let ln_p = p.ln();
+        (1..points).find_map(|k| {
+            let (p0, p1) = (self.pressure[k - 1], self.pressure[k]);
+            let brackets = (p0 <= p && p <= p1) || (p1 <= p && p <= p0);
+            let usable = brackets && p0 != p1 && p0 > 0.0 && p1 > 0.0;
+            if !usable || !on_branch(k - 1) || !on_branch(k) {
+                return None;
+            }
+            let (t0, t1) = (self.temperature[k - 1], self.temperature[k]);
+            let w = (ln_p - p0.ln()) / (p1.ln() - p0.ln());
+            Some(t0 + w * (t1 - t0))
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Spinodal-curve sample points from CoolProp.
 pub struct SpinodalCurve {
     /// Reduced inverse temperature `tau = Tc / T`.
@@ -88,6 +211,7 @@ pub struct SpinodalCurve {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Critical point candidate returned by CoolProp for mixtures.
 pub struct CriticalPoint {
     /// Temperature of the critical point, in kelvin.
@@ -100,6 +224,168 @@ pub struct CriticalPoint {
     pub stable: bool,
 }
 
+/// Zero-based component index, checked against the component count of a state.
+///
+/// The per-component setters and getters such as [`AbstractState::get_fugacity`] accept either a
+/// raw `c_long` or a `ComponentIndex`. A raw index goes to CoolProp unchecked, while a
+/// `ComponentIndex` can only be built for an index that exists in the state passed to
+/// [`new`](Self::new). The check is made once, at construction; an index built for one state is
+/// not re-validated when used with another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ComponentIndex(c_long);
+
+impl ComponentIndex {
+    /// Validate `index` against [`AbstractState::component_count`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if `index` is not below the component count of `state`,
+    /// or the error from reading the component names.
+    pub fn new(state: &AbstractState, index: usize) -> Result<Self> {
+        let count = state.component_count()?;
+        if index >= count {
+            return Err(Error::InvalidInput(format!(
+                "component index {index} is out of range for {count} component(s)"
+            )));
+        }
+        Ok(Self(index as c_long))
+    }
+
+    /// The index as passed to CoolProp.
+    #[inline]
+    pub fn get(self) -> c_long {
+        self.0
+    }
+}
+
+impl From<ComponentIndex> for c_long {
+    #[inline]
+    fn from(index: ComponentIndex) -> Self {
+        index.0
+    }
+}
+
+/// A model parameter set through one of the [`AbstractState`] mutators.
+#[derive(Debug, Clone)]
+enum ModelOverride {
+    BinaryInteraction {
+        i: c_long,
+        j: c_long,
+        parameter: CString,
+        value: f64,
+    },
+    CubicAlphaC {
+        i: c_long,
+        parameter: CString,
+        c: [f64; 3],
+    },
+    FluidParameter {
+        i: c_long,
+        parameter: CString,
+        value: f64,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Timing and outcome of a single [`AbstractState::update_with_stats`] call.
+pub struct UpdateStats {
+    /// Wall-clock time spent in the update.
+    pub elapsed: Duration,
+    /// Phase of the state after the update.
+    pub phase: Phase,
+}
+
+/// Lazily evaluates one output over a stream of input pairs.
+///
+/// Each call to [`next`](Iterator::next) pulls one `(v1, v2)` pair from `inputs`, updates the
+/// wrapped state and reads `output`, so memory use stays constant however long the stream is.
+/// Prefer [`AbstractState::update_and_1_out`] when the inputs already sit in memory: it
+/// evaluates the whole batch in a single FFI call.
+///
+/// A failed point yields an `Err` for that item only; the stream carries on with the next pair.
+pub struct PropertyStream<'a, I> {
+    state: &'a mut AbstractState,
+    pair: InputPair,
+    output: Param,
+    inputs: I,
+}
+
+impl<'a, I> PropertyStream<'a, I>
+where
+    I: Iterator<Item = (f64, f64)>,
+{
+    /// Stream `output` over `inputs`, interpreting each pair according to `pair`.
+    pub fn new(
+        state: &'a mut AbstractState,
+        pair: InputPair,
+        output: Param,
+        inputs: impl IntoIterator<IntoIter = I>,
+    ) -> Self {
+        Self {
+            state,
+            pair,
+            output,
+            inputs: inputs.into_iter(),
+        }
+    }
+}
+
+impl<I> Iterator for PropertyStream<'_, I>
+where
+    I: Iterator<Item = (f64, f64)>,
+{
+    type Item = Result<f64>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (v1, v2) = self.inputs.next()?;
+        Some(
+            self.state
+                .update(self.pair, v1, v2)
+                .and_then(|()| self.state.get(self.output)),
+        )
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inputs.size_hint()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+/// Reduced Helmholtz energy `a / (R T) = alpha0(tau, delta) + alphar(tau, delta)` and its
+/// derivatives, returned by [`AbstractState::helmholtz_derivatives`].
+///
+/// `tau = T_reducing / T` and `delta = rho / rho_reducing`. Every derivative with respect to
+/// `tau` is taken at constant `delta` and vice versa. The ideal-gas part has no mixed derivative
+/// because `alpha0` separates into a `tau` term and `ln(delta)`.
+pub struct HelmholtzDerivs {
+    /// Reciprocal reduced temperature `tau`.
+    pub tau: f64,
+    /// Reduced density `delta`.
+    pub delta: f64,
+    /// Ideal-gas part `alpha0`.
+    pub alpha0: f64,
+    /// `d(alpha0)/d(tau)`.
+    pub dalpha0_dtau: f64,
+    /// `d(alpha0)/d(delta)`.
+    pub dalpha0_ddelta: f64,
+    /// `d2(alpha0)/d(tau)2`, derived from the ideal-gas heat capacity.
+    pub d2alpha0_dtau2: f64,
+    /// `d2(alpha0)/d(delta)2`.
+    pub d2alpha0_ddelta2: f64,
+    /// Residual part `alphar`.
+    pub alphar: f64,
+    /// `d(alphar)/d(tau)`.
+    pub dalphar_dtau: f64,
+    /// `d(alphar)/d(delta)`.
+    pub dalphar_ddelta: f64,
+    /// `d2(alphar)/d(tau)2`, derived from the isochoric heat capacity.
+    pub d2alphar_dtau2: f64,
+    /// `d2(alphar)/d(delta)2`, derived from `(dp/drho)_T`.
+    pub d2alphar_ddelta2: f64,
+    /// `d2(alphar)/d(delta)d(tau)`, derived from `(dp/dT)_rho`.
+    pub d2alphar_ddelta_dtau: f64,
+}
+
 impl AbstractState {
     /// Create a new CoolProp state object for the selected backend and fluid.
     ///
@@ -127,6 +413,10 @@ impl AbstractState {
         Ok(Self {
             indices,
             handle,
+            last_update: None,
+            updated: false,
+            string_capacity: DEFAULT_STR_BUF_LEN,
+            model_overrides: Vec::new(),
             _not_sync: PhantomData,
         })
     }
@@ -153,10 +443,40 @@ impl AbstractState {
         if let Ok(fractions) = self.mole_fractions() {
             let _ = cloned.set_fractions(&fractions);
         }
+        cloned.string_capacity = self.string_capacity;
 
         Ok(cloned)
     }
 
+    /// [`try_clone`](Self::try_clone), with the parameters set through the model mutators, such
+    /// as [`set_binary_interaction_double`](Self::set_binary_interaction_double), applied again
+    /// in the order they were set. Used by helpers that probe other states, so a tuned mixture
+    /// gives the same results there as on `self`. The imposed phase is not copied.
+    fn model_clone(&self) -> Result<Self> {
+        let mut cloned = self.try_clone()?;
+        for model_override in &self.model_overrides {
+            cloned.apply_model_override(model_override.clone())?;
+        }
+        Ok(cloned)
+    }
+
+    /// Reset to a clean, un-updated state by replacing the underlying handle.
+    ///
+    /// A fresh handle is built as in [`try_clone`](Self::try_clone), keeping the backend, fluids,
+    /// and mole fractions, and the old handle is released. Everything else is discarded: the
+    /// thermodynamic state, imposed phase, and any parameters set through the model mutators
+    /// such as [`set_binary_interaction_double`](Self::set_binary_interaction_double).
+    /// State-dependent [`get`](Self::get) calls fail again until the next
+    /// [`update`](Self::update).
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from reconstructing the state; `self` is left untouched in that case.
+    pub fn clear(&mut self) -> Result<()> {
+        *self = self.try_clone()?;
+        Ok(())
+    }
+
     /// Raw CoolProp handle for advanced FFI integrations.
     ///
     /// Most users should rely on the safe wrappers; this accessor exists so that external callers
@@ -174,15 +494,91 @@ impl AbstractState {
     /// backend. Convenience helpers such as [`update_dmolar_t`](Self::update_dmolar_t) delegate to
     /// this method.
     ///
+    /// The pair and values of the most recent call are recorded, with their units, in the
+    /// [`Debug`](fmt::Debug) output of the state, whether or not the update succeeded.
+    ///
     /// # Errors
     ///
-    /// Propagates CoolProp errors (invalid pair for current phase, out-of-range inputs, etc.).
+    /// Propagates CoolProp errors (invalid pair for current phase, out-of-range inputs, etc.) as
+    /// [`Error::CoolProp`] with CoolProp's error code. The inputs are prepended to the message,
+    /// so it reads like `update(PT_INPUTS, v1=101325 Pa, v2=300 K) failed: <CoolProp message>`.
     #[inline]
     pub fn update(&mut self, pair: InputPair, v1: f64, v2: f64) -> Result<()> {
         let id = self.indices.id_of_pair(pair);
-        call_with_error(|err, msg, len| unsafe {
+        self.last_update = Some((pair, v1, v2));
+        let result = call_with_error(|err, msg, len| unsafe {
             crate::ffi::AbstractState_update(self.handle, id, v1, v2, err, msg, len);
         })
+        .map_err(|err| match err {
+            Error::CoolProp { code, message } => Error::CoolProp {
+                code,
+                message: format!("{} failed: {message}", describe_update(pair, v1, v2)),
+            },
+            other => other,
+        });
+        self.updated |= result.is_ok();
+        result
+    }
+
+    /// Update the state, retrying with slightly perturbed inputs if CoolProp fails to converge.
+    ///
+    /// Near phase boundaries a flash can fail for inputs that are only marginally off. When the
+    /// initial [`update`](Self::update) fails with a convergence-type error, the inputs are scaled
+    /// by `1 ± rel_jitter` (then `1 ± 2 * rel_jitter`) in every sign combination and the first
+    /// successful update is kept. An input that is exactly zero, such as an enthalpy at the
+    /// reference state, is shifted by `± rel_jitter` in its SI unit instead, and a vapor quality
+    /// is clamped to `[0, 1]`. On success the state reflects the *perturbed* inputs, not the
+    /// exact `v1`/`v2` requested; query the state if the exact values matter.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if `rel_jitter` is not finite and positive. Non-convergence
+    /// errors are returned immediately; if every retry fails, the original error is returned.
+    pub fn update_with_perturbation(
+        &mut self,
+        pair: InputPair,
+        v1: f64,
+        v2: f64,
+        rel_jitter: f64,
+    ) -> Result<()> {
+        if !(rel_jitter.is_finite() && rel_jitter > 0.0) {
+            return Err(Error::InvalidInput(format!(
+                "rel_jitter must be finite and positive, got {rel_jitter}"
+            )));
+        }
+        let original = match self.update(pair, v1, v2) {
+            Ok(()) => return Ok(()),
+            Err(err) if is_convergence_failure(&err) => err,
+            Err(err) => return Err(err),
+        };
+        let (param1, param2) = pair.params();
+        for (s1, s2) in PERTURBATION_STEPS {
+            let p1 = perturbed_input(param1, v1, s1 * rel_jitter);
+            let p2 = perturbed_input(param2, v2, s2 * rel_jitter);
+            if self.update(pair, p1, p2).is_ok() {
+                return Ok(());
+            }
+        }
+        Err(original)
+    }
+
+    /// [`update`](Self::update), also reporting how long the flash took and the resulting phase.
+    ///
+    /// Meant for profiling large batch runs to find the input regions where flashes are slow.
+    /// Only the update itself is timed; the phase lookup afterwards is not included. CoolProp
+    /// offers no hook into its solver iterations, so intermediate steps cannot be observed.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`update`](Self::update), plus any error from [`phase`](Self::phase).
+    pub fn update_with_stats(&mut self, pair: InputPair, v1: f64, v2: f64) -> Result<UpdateStats> {
+        let started = Instant::now();
+        self.update(pair, v1, v2)?;
+        let elapsed = started.elapsed();
+        Ok(UpdateStats {
+            elapsed,
+            phase: self.phase()?,
+        })
     }
 
     /// Retrieve a scalar property identified by [`Param`].
@@ -190,26 +586,145 @@ impl AbstractState {
     /// The state must be up to date before calling this method. Many `Param` variants refer to
     /// mass- or molar-specific values; ensure that downstream calculations use consistent bases.
     ///
+    /// The value is returned as-is, including NaN or infinite results; use
+    /// [`get_checked`](Self::get_checked) to reject those as errors.
+    ///
     /// # Errors
     ///
-    /// Returns the underlying CoolProp error if the property cannot be computed (e.g., outside the
-    /// model's domain).
+    /// Returns [`Error::InvalidInput`] if `param` is state-dependent (see [`Param::is_trivial`])
+    /// and no update has succeeded yet. Otherwise returns the underlying CoolProp error if the
+    /// property cannot be computed (e.g., outside the model's domain).
     #[inline]
     pub fn get(&self, param: Param) -> Result<f64> {
+        if !self.updated && !param.is_trivial() {
+            return Err(Error::InvalidInput(
+                "state not initialized; call update first".into(),
+            ));
+        }
         let id = self.indices.id_of_param(param);
         call_with_error(|err, msg, len| unsafe {
             crate::ffi::AbstractState_keyed_output(self.handle, id, err, msg, len)
         })
     }
 
+    /// Retrieve a scalar property, treating non-finite results as errors.
+    ///
+    /// [`get`](Self::get) only fails when CoolProp raises an error and otherwise returns the raw
+    /// value, which may be NaN or infinite. This variant applies the same policy as
+    /// [`props_si`](crate::props_si): a non-finite value becomes an [`Error::Computation`]
+    /// carrying CoolProp's global error string. Choose per call which policy fits.
+    pub fn get_checked(&self, param: Param) -> Result<f64> {
+        let value = self.get(param)?;
+        crate::check_finite_and_report_error(
+            value,
+            &format!("AbstractState::get({})", param.as_coolprop_str()),
+        )
+    }
+
+    /// Retrieve a scalar property by its CoolProp name, for outputs [`Param`] does not cover.
+    ///
+    /// Any name or alias CoolProp's `get_param_index` accepts works, for example `"D"` for
+    /// `Dmass`. The index of each name is looked up once and cached for the process. Prefer
+    /// [`get`](Self::get) where a `Param` exists. Unlike `get`, calling this before any update
+    /// is not rejected up front; CoolProp reports the error instead.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if CoolProp does not know `output`, [`Error::EmbeddedNul`]
+    /// if it contains a NUL byte, and otherwise the CoolProp error for the output.
+    pub fn get_by_name(&self, output: &str) -> Result<f64> {
+        let id = named_param_id(output)?;
+        call_with_error(|err, msg, len| unsafe {
+            crate::ffi::AbstractState_keyed_output(self.handle, id, err, msg, len)
+        })
+    }
+
+    /// Update the state from two properties given by [`Param`], in any order.
+    ///
+    /// The input pair is resolved with [`InputPair::try_from_params`] and the values are swapped
+    /// into the order CoolProp expects, so `update_from_params(Param::T, 300.0, Param::P, 1e5)` is
+    /// the same as `update(InputPair::PT, 1e5, 300.0)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if no input pair combines `a` and `b`, and otherwise the
+    /// same errors as [`update`](Self::update).
+    pub fn update_from_params(&mut self, a: Param, va: f64, b: Param, vb: f64) -> Result<()> {
+        let pair = InputPair::try_from_params(a, b).ok_or_else(|| {
+            Error::InvalidInput(format!(
+                "no input pair combines {} and {}",
+                a.as_coolprop_str(),
+                b.as_coolprop_str()
+            ))
+        })?;
+        if pair.params().0 == a {
+            self.update(pair, va, vb)
+        } else {
+            self.update(pair, vb, va)
+        }
+    }
+
     /// Update the state using molar density and temperature.
     ///
-    /// Shorthand for `update(InputPair::DmolarT, dmolar, t)`.
+    /// Shorthand for `update(InputPair::DmolarT, dmolar, t)`. In debug builds with the `log`
+    /// feature enabled, a warning is logged when `dmolar` is implausibly high for the fluid, or
+    /// when it is gas-like as a molar density but liquid-like read as a mass density in kg/m^3.
+    /// The latter is what a mass density passed by mistake looks like, since the molar mass is
+    /// below 1 kg/mol and the mass value is numerically the smaller one.
     #[inline]
     pub fn update_dmolar_t(&mut self, dmolar: f64, t: f64) -> Result<()> {
+        #[cfg(all(feature = "log", debug_assertions))]
+        {
+            self.warn_implausible_density(Param::RhomolarReducing, dmolar, "mol/m^3");
+            self.warn_mass_density_as_molar(dmolar);
+        }
         self.update(InputPair::DmolarT, dmolar, t)
     }
 
+    /// Update the state using mass density and temperature.
+    ///
+    /// Shorthand for `update(InputPair::DmassT, dmass, t)`. In debug builds with the `log`
+    /// feature enabled, a warning is logged when `dmass` is implausibly high for the fluid,
+    /// which usually means a molar density was passed by mistake.
+    #[inline]
+    pub fn update_dmass_t(&mut self, dmass: f64, t: f64) -> Result<()> {
+        #[cfg(all(feature = "log", debug_assertions))]
+        self.warn_implausible_density(Param::RhomassReducing, dmass, "kg/m^3");
+        self.update(InputPair::DmassT, dmass, t)
+    }
+
+    /// Log a warning if `density` exceeds what the fluid can plausibly reach.
+    ///
+    /// Only the upper side is checked: gases legitimately span many orders of magnitude below the
+    /// reducing density, so a too-low value cannot be told apart from a dilute state.
+    #[cfg(all(feature = "log", debug_assertions))]
+    fn warn_implausible_density(&self, reducing: Param, density: f64, unit: &str) {
+        if let Ok(rho_reducing) = self.get(reducing)
+            && density_is_implausible(density, rho_reducing)
+        {
+            log::warn!(
+                "density {density} {unit} is more than {MAX_REDUCED_DENSITY} times the reducing \
+                 density {rho_reducing} {unit}; check for a molar/mass density mix-up"
+            );
+        }
+    }
+
+    /// Log a warning if the molar density `dmolar` reads like a liquid mass density.
+    #[cfg(all(feature = "log", debug_assertions))]
+    fn warn_mass_density_as_molar(&self, dmolar: f64) {
+        if let (Ok(rho_reducing), Ok(molar_mass)) = (
+            self.get(Param::RhomolarReducing),
+            self.get(Param::MolarMass),
+        ) && molar_density_reads_as_mass(dmolar, rho_reducing, molar_mass)
+        {
+            log::warn!(
+                "density {dmolar} mol/m^3 is gas-like, but as kg/m^3 it is a liquid density of \
+                 {:.0} mol/m^3; check for a molar/mass density mix-up",
+                dmolar / molar_mass
+            );
+        }
+    }
+
     /// Current pressure in pascals.
     ///
     /// Equivalent to `get(Param::P)`.
@@ -218,6 +733,31 @@ impl AbstractState {
         self.get(Param::P)
     }
 
+    /// Molar mass of the fluid as a whole, in kg/mol.
+    ///
+    /// Equivalent to `get(Param::MolarMass)`, named to remove the ambiguity for mixtures: this
+    /// is the composition-weighted average `sum(x_i * M_i)` over the current mole fractions, not
+    /// the molar mass of any single component. Use it to convert between molar and mass bases
+    /// of the mixture; per-component molar masses come from pure-fluid states.
+    #[inline]
+    pub fn average_molar_mass(&self) -> Result<f64> {
+        self.get(Param::MolarMass)
+    }
+
+    /// Isobaric and isochoric specific heat capacities `(cp, cv)` on a mass basis, in J/(kg*K).
+    ///
+    /// Both are read from the current state; their ratio is the heat capacity ratio used in
+    /// gas-dynamics relations. See [`heat_capacities_molar`](Self::heat_capacities_molar) for
+    /// the molar basis.
+    pub fn heat_capacities(&self) -> Result<(f64, f64)> {
+        Ok((self.get(Param::Cpmass)?, self.get(Param::Cvmass)?))
+    }
+
+    /// Isobaric and isochoric heat capacities `(cp, cv)` on a molar basis, in J/(mol*K).
+    pub fn heat_capacities_molar(&self) -> Result<(f64, f64)> {
+        Ok((self.get(Param::Cpmolar)?, self.get(Param::Cvmolar)?))
+    }
+
     /// Impose a phase classification prior to the next state update.
     ///
     /// Some iterative schemes benefit from constraining CoolProp to a specific phase branch.
@@ -253,6 +793,35 @@ impl AbstractState {
         Ok(crate::c_buf_to_string(&buffer))
     }
 
+    /// Like [`fluid_names`](Self::fluid_names), but fails with [`Error::InvalidUtf8`] instead of
+    /// lossily replacing bytes that are not valid UTF-8.
+    pub fn fluid_names_strict(&self) -> Result<String> {
+        let mut buffer = [0 as c_char; DEFAULT_STR_BUF_LEN];
+        call_with_error(|err, msg, len| unsafe {
+            crate::ffi::AbstractState_fluid_names(self.handle, buffer.as_mut_ptr(), err, msg, len);
+        })?;
+        crate::try_c_buf_to_string(&buffer)
+    }
+
+    /// Component names of the fluid, in the order CoolProp uses for composition vectors.
+    ///
+    /// This splits [`fluid_names`](Self::fluid_names) on the `&` or `,` separators; a pure fluid
+    /// yields a single entry.
+    pub fn fluid_names_vec(&self) -> Result<Vec<String>> {
+        Ok(self
+            .fluid_names()?
+            .split(['&', ','])
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_owned)
+            .collect())
+    }
+
+    /// Number of components in the fluid; `1` for a pure fluid.
+    pub fn component_count(&self) -> Result<usize> {
+        Ok(self.fluid_names_vec()?.len())
+    }
+
     /// Name of the active CoolProp backend (e.g., `"HEOS"`, `"REFPROP"`).
     pub fn backend_name(&self) -> Result<String> {
         let mut buffer = [0 as c_char; DEFAULT_STR_BUF_LEN];
@@ -262,6 +831,16 @@ impl AbstractState {
         Ok(crate::c_buf_to_string(&buffer))
     }
 
+    /// Like [`backend_name`](Self::backend_name), but fails with [`Error::InvalidUtf8`] instead
+    /// of lossily replacing bytes that are not valid UTF-8.
+    pub fn backend_name_strict(&self) -> Result<String> {
+        let mut buffer = [0 as c_char; DEFAULT_STR_BUF_LEN];
+        call_with_error(|err, msg, len| unsafe {
+            crate::ffi::AbstractState_backend_name(self.handle, buffer.as_mut_ptr(), err, msg, len);
+        })?;
+        crate::try_c_buf_to_string(&buffer)
+    }
+
     /// Query a string-valued fluid parameter.
     ///
     /// `param` uses the CoolProp keyword (such as `"aliases"` or `"CAS"`). The returned string is
@@ -276,10 +855,9 @@ impl AbstractState {
             label: "param",
             source,
         })?;
-        let mut capacity = DEFAULT_STR_BUF_LEN;
-        loop {
+        grow_buffer(BufferGrowth::new(self.string_capacity), |capacity| {
             let mut buffer = vec![0 as c_char; capacity];
-            match call_with_error(|err, msg, buflen| unsafe {
+            call_with_error(|err, msg, buflen| unsafe {
                 crate::ffi::AbstractState_fluid_param_string(
                     self.handle,
                     param.as_ptr(),
@@ -289,16 +867,23 @@ impl AbstractState {
                     msg,
                     buflen,
                 );
-            }) {
-                Ok(()) if !buffer_saturated(&buffer) => {
-                    return Ok(crate::c_buf_to_string(&buffer));
-                }
-                Ok(()) => {
-                    capacity *= 2;
-                }
-                Err(err) => return Err(err),
+            })?;
+            if buffer_saturated(&buffer) {
+                Ok(Fill::Grow(0))
+            } else {
+                Ok(Fill::Done(crate::c_buf_to_string(&buffer)))
             }
-        }
+        })
+    }
+
+    /// Set the buffer size, in bytes, that [`fluid_param_string`](Self::fluid_param_string)
+    /// starts from.
+    ///
+    /// Outputs that do not fit are retried with a doubled buffer, so this is only a performance
+    /// knob: raise it when reading large fields such as `"JSON"` to skip the intermediate
+    /// attempts. The setting is kept by [`try_clone`](Self::try_clone). Defaults to 1024 bytes.
+    pub fn set_string_capacity(&mut self, bytes: usize) {
+        self.string_capacity = bytes.max(1);
     }
 
     /// Determine the current thermodynamic phase classification.
@@ -312,6 +897,25 @@ impl AbstractState {
         Phase::from_code(code).ok_or(Error::UnknownPhaseCode(code as i64))
     }
 
+    /// Whether the current state lies above the critical point.
+    ///
+    /// Returns `true` for [`Phase::Supercritical`], [`Phase::SupercriticalGas`], and
+    /// [`Phase::SupercriticalLiquid`].
+    pub fn is_supercritical(&self) -> Result<bool> {
+        Ok(matches!(
+            self.phase()?,
+            Phase::Supercritical | Phase::SupercriticalGas | Phase::SupercriticalLiquid
+        ))
+    }
+
+    /// Whether the current state lies inside the saturation dome ([`Phase::TwoPhase`]).
+    ///
+    /// Useful after updates such as `DmassP` or `HmassP` whose result may land on either side of
+    /// the dome. When this returns `true`, `get(Param::Q)` gives the vapor quality.
+    pub fn is_two_phase(&self) -> Result<bool> {
+        Ok(self.phase()? == Phase::TwoPhase)
+    }
+
     /// Property evaluation at the saturated liquid state associated with the current conditions.
     pub fn saturated_liquid_keyed_output(&self, param: Param) -> Result<f64> {
         let id = self.indices.id_of_param(param);
@@ -328,6 +932,105 @@ impl AbstractState {
         })
     }
 
+    /// Latent heat still needed to fully evaporate a saturated mixture of quality `q`, in J/kg.
+    ///
+    /// Returns `(1 - q) * h_fg`, where the mass-specific heat of vaporization
+    /// `h_fg = h_vap - h_liq` comes from [`saturated_vapor_keyed_output`] and
+    /// [`saturated_liquid_keyed_output`] at the current saturation condition. `q = 0` gives the
+    /// full `h_fg` and `q = 1` gives zero; `q * h_fg` is the latent heat already absorbed.
+    ///
+    /// [`saturated_vapor_keyed_output`]: Self::saturated_vapor_keyed_output
+    /// [`saturated_liquid_keyed_output`]: Self::saturated_liquid_keyed_output
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if `q` is not in `[0, 1]`, and propagates CoolProp errors
+    /// if the state has no associated saturation condition.
+    pub fn latent_heat_at_quality(&self, q: f64) -> Result<f64> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(Error::InvalidInput(format!(
+                "quality must be in [0, 1], got {q}"
+            )));
+        }
+        let h_fg = self.saturated_vapor_keyed_output(Param::Hmass)?
+            - self.saturated_liquid_keyed_output(Param::Hmass)?;
+        Ok((1.0 - q) * h_fg)
+    }
+
+    /// Homogeneous density of a saturated mixture of quality `q`, in kg/m^3.
+    ///
+    /// Specific volumes, not densities, add by mass fraction, so the result is
+    /// `1 / ((1 - q) / rho_liq + q / rho_vap)` with the saturated densities `Dmass` from
+    /// [`saturated_liquid_keyed_output`](Self::saturated_liquid_keyed_output) and
+    /// [`saturated_vapor_keyed_output`](Self::saturated_vapor_keyed_output). Averaging the two
+    /// densities linearly overestimates it badly at low quality.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if `q` is not in `[0, 1]`, and propagates CoolProp errors
+    /// if the state has no associated saturation condition.
+    pub fn two_phase_density(&self, q: f64) -> Result<f64> {
+        if !(0.0..=1.0).contains(&q) {
+            return Err(Error::InvalidInput(format!(
+                "quality must be in [0, 1], got {q}"
+            )));
+        }
+        let rho_liq = self.saturated_liquid_keyed_output(Param::Dmass)?;
+        let rho_vap = self.saturated_vapor_keyed_output(Param::Dmass)?;
+        Ok(1.0 / ((1.0 - q) / rho_liq + q / rho_vap))
+    }
+
+    /// Phase-equilibrium residual `g_liq - g_vap` of the mass-specific Gibbs energies, in J/kg.
+    ///
+    /// For a pure fluid in a correctly converged saturation state both phases have the same
+    /// Gibbs energy, so the result should be close to zero relative to either value. Both come
+    /// from the saturated keyed outputs for `Gmass`. For mixtures the phase compositions differ
+    /// and the equality holds per component through chemical potentials, not for `Gmass`.
+    pub fn saturation_gibbs_equality_residual(&self) -> Result<f64> {
+        Ok(self.saturated_liquid_keyed_output(Param::Gmass)?
+            - self.saturated_vapor_keyed_output(Param::Gmass)?)
+    }
+
+    /// Heat capacity along the saturation curve `c_sigma` of one saturated branch, in J/(kg*K).
+    ///
+    /// Evaluated at the current temperature on the saturated liquid ([`Phase::Liquid`]) or vapor
+    /// ([`Phase::Gas`]) as
+    ///
+    /// `c_sigma = c_p - T (dv/dT)_P (dP/dT)_sat`,
+    ///
+    /// with `v = 1 / rho` on a mass basis, `(dv/dT)_P = -(d rho/dT)_P / rho^2` from
+    /// [`first_partial_deriv`](Self::first_partial_deriv), and the Clausius-Clapeyron slope
+    /// `(dP/dT)_sat` from [`first_saturation_deriv`](Self::first_saturation_deriv). It is the heat
+    /// needed per kelvin to keep the fluid saturated while heating it; for the vapor of many
+    /// fluids, including water, it is negative. The branch is evaluated on a clone made with
+    /// [`try_clone`](Self::try_clone) that carries over the model parameters set on `self`,
+    /// such as binary interaction parameters, so `self` is not modified. An imposed phase is
+    /// not carried over, since the branch is reached through a `QT` flash.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] for phases other than liquid and gas, and propagates
+    /// CoolProp errors, for example at or above the critical temperature.
+    pub fn saturated_heat_capacity(&self, phase: Phase) -> Result<f64> {
+        let q = match phase {
+            Phase::Liquid => 0.0,
+            Phase::Gas => 1.0,
+            _ => {
+                return Err(Error::InvalidInput(format!(
+                    "saturated heat capacity needs the liquid or gas branch, got {phase:?}"
+                )));
+            }
+        };
+        let t = self.get(Param::T)?;
+        let mut branch = self.model_clone()?;
+        branch.update(InputPair::QT, q, t)?;
+        let cp = branch.get(Param::Cpmass)?;
+        let rho = branch.get(Param::Dmass)?;
+        let dv_dt = -branch.first_partial_deriv(Param::Dmass, Param::T, Param::P)? / (rho * rho);
+        let dp_dt = branch.first_saturation_deriv(Param::P, Param::T)?;
+        Ok(cp - t * dv_dt * dp_dt)
+    }
+
     /// Property evaluation for an explicit saturation phase (`liquid`, `gas`, or `twophase`).
     ///
     /// Fails if the supplied `phase` lacks a saturation token (e.g., supercritical states).
@@ -381,7 +1084,275 @@ impl AbstractState {
         })
     }
 
+    /// Molar enthalpy of the current state split as `(ideal_gas, residual)`, in J/mol.
+    ///
+    /// The two parts always sum to `Hmolar`: the ideal-gas part is the enthalpy the fluid would
+    /// have as an ideal gas at the same temperature and density (including the reference-state
+    /// offset), and the residual part is the contribution of intermolecular forces. A residual
+    /// that is large relative to the total marks states where nonideality dominates, such as
+    /// liquids and dense supercritical fluids.
+    pub fn enthalpy_split(&self) -> Result<(f64, f64)> {
+        Ok((
+            self.get(Param::HmolarIdealgas)?,
+            self.get(Param::HmolarResidual)?,
+        ))
+    }
+
+    /// Mass-specific counterpart of [`enthalpy_split`](Self::enthalpy_split), in J/kg, summing to
+    /// `Hmass`.
+    pub fn enthalpy_split_mass(&self) -> Result<(f64, f64)> {
+        let (ideal_gas, residual) = self.enthalpy_split()?;
+        let molar_mass = self.get(Param::MolarMass)?;
+        Ok((ideal_gas / molar_mass, residual / molar_mass))
+    }
+
+    /// Value of `output` after an isentropic change from the current state to pressure `p2` (Pa).
+    ///
+    /// The current molar entropy is read and a clone made with [`try_clone`](Self::try_clone) is
+    /// updated to `(p2, s)`, so `self` is never modified. The clone carries over the model
+    /// parameters set on `self`, such as binary interaction parameters, but not an imposed
+    /// phase, which need not hold at `p2`. Useful for probing ideal compressor or expander outlets
+    /// from a reference state that other code still relies on.
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from reading the entropy, cloning the state, or the `PSmolar` flash.
+    pub fn property_at_isentropic(&self, output: Param, p2: f64) -> Result<f64> {
+        let s = self.get(Param::Smolar)?;
+        let mut probe = self.model_clone()?;
+        probe.update(InputPair::PSmolar, p2, s)?;
+        probe.get(output)
+    }
+
+    /// Specific flow exergy `psi = (h - h0) - T0 (s - s0)` relative to the dead state
+    /// `(t0, p0)`, in J/kg.
+    ///
+    /// `h0` and `s0` are the mass-specific enthalpy and entropy at `t0` (K) and `p0` (Pa),
+    /// evaluated on a clone made with [`try_clone`](Self::try_clone), so `self` is not
+    /// modified. The clone carries over the model parameters set on `self`, such as binary
+    /// interaction parameters, but not an imposed phase, which need not hold at the dead state.
+    /// Kinetic and potential energy are not included, and for mixtures the dead state keeps the
+    /// current composition (no chemical exergy).
+    ///
+    /// # Errors
+    ///
+    /// Propagates errors from reading the current state, cloning it, or the `PT` update to the
+    /// dead state, which fails if `(t0, p0)` lies exactly on the saturation curve.
+    pub fn specific_exergy(&self, t0: f64, p0: f64) -> Result<f64> {
+        let h = self.get(Param::Hmass)?;
+        let s = self.get(Param::Smass)?;
+        let mut dead = self.model_clone()?;
+        dead.update(InputPair::PT, p0, t0)?;
+        let h0 = dead.get(Param::Hmass)?;
+        let s0 = dead.get(Param::Smass)?;
+        Ok((h - h0) - t0 * (s - s0))
+    }
+
+    /// Mach number of a flow at `velocity` (m/s) through the current state.
+    ///
+    /// The speed of sound is the real-gas value from the equation of state, not the ideal-gas
+    /// `sqrt(gamma R T)`; the two differ noticeably for dense gases and near the critical point.
+    pub fn mach_number(&self, velocity: f64) -> Result<f64> {
+        Ok(velocity / self.get(Param::SpeedOfSound)?)
+    }
+
+    /// Joule-Thomson coefficient `(dT/dP)_h` of the current state, in K/Pa.
+    ///
+    /// Positive values mean the fluid cools on isenthalpic expansion.
+    pub fn joule_thomson(&self) -> Result<f64> {
+        self.first_partial_deriv(Param::T, Param::P, Param::Hmolar)
+    }
+
+    /// `(d rho / d T)_P` of the current state on a mass basis, in kg/(m^3*K).
+    pub fn density_temperature_derivative_isobaric(&self) -> Result<f64> {
+        self.first_partial_deriv(Param::Dmass, Param::T, Param::P)
+    }
+
+    /// `(d rho / d P)_T` of the current state on a mass basis, in kg/(m^3*Pa).
+    pub fn density_pressure_derivative_isothermal(&self) -> Result<f64> {
+        self.first_partial_deriv(Param::Dmass, Param::P, Param::T)
+    }
+
+    /// `(d h / d P)_T` of the current state on a mass basis, in J/(kg*Pa).
+    pub fn enthalpy_pressure_derivative_isothermal(&self) -> Result<f64> {
+        self.first_partial_deriv(Param::Hmass, Param::P, Param::T)
+    }
+
+    /// `(d P / d T)_rho` of the current state, in Pa/K.
+    pub fn pressure_temperature_derivative_isochoric(&self) -> Result<f64> {
+        self.first_partial_deriv(Param::P, Param::T, Param::Dmass)
+    }
+
+    /// Isothermal compressibility `kappa_T = -(1/v) (dv/dP)_T` of the current state, in 1/Pa.
+    pub fn isothermal_compressibility(&self) -> Result<f64> {
+        self.get(Param::IsothermalCompressibility)
+    }
+
+    /// Isothermal bulk modulus `K_T = 1 / kappa_T` of the current state, in Pa.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::Computation`] if the compressibility is zero, and propagates errors from
+    /// [`isothermal_compressibility`](Self::isothermal_compressibility).
+    pub fn bulk_modulus(&self) -> Result<f64> {
+        let kappa = self.isothermal_compressibility()?;
+        if kappa == 0.0 {
+            return Err(Error::Computation {
+                context: "bulk_modulus".into(),
+                message: "isothermal compressibility is zero".into(),
+            });
+        }
+        Ok(1.0 / kappa)
+    }
+
+    /// Local isentropic exponent `n = -(v/P) (dP/dv)_s` of the current state, dimensionless.
+    ///
+    /// Evaluated on a mass basis as `(rho/P) (dP/d rho)_s`, using
+    /// [`first_partial_deriv`](Self::first_partial_deriv) of `P` with respect to `Dmass` at
+    /// constant `Smass`; this is the isentropic bulk modulus divided by pressure. For an ideal
+    /// gas it reduces to `cp/cv`. Using it as the exponent of `P v^n = const` in compressor or
+    /// expander calculations assumes a reversible adiabatic path along which `n` stays close to
+    /// its value at this state; for real compressions with large pressure ratios, average it
+    /// over the inlet and outlet or use the end-state enthalpies instead.
+    pub fn polytropic_exponent(&self) -> Result<f64> {
+        let dp_drho = self.first_partial_deriv(Param::P, Param::Dmass, Param::Smass)?;
+        Ok(self.get(Param::Dmass)? / self.get(Param::P)? * dp_drho)
+    }
+
+    /// Grüneisen parameter `Γ = v (dP/du)_v` of the current state, dimensionless.
+    ///
+    /// Evaluated on a mass basis as `first_partial_deriv(P, Umass, Dmass) / Dmass`: holding the
+    /// density constant holds the specific volume `v = 1 / rho` constant. This equals
+    /// `(dP/dT)_v / (rho c_v)` and tends to `gamma - 1` for an ideal gas.
+    pub fn gruneisen_parameter(&self) -> Result<f64> {
+        let dp_du = self.first_partial_deriv(Param::P, Param::Umass, Param::Dmass)?;
+        Ok(dp_du / self.get(Param::Dmass)?)
+    }
+
+    /// Upper Joule-Thomson inversion temperature at pressure `p` (Pa), in kelvin.
+    ///
+    /// The fluid's `[T_min, T_max]` range is scanned downward from `T_max` on a 64-point grid for
+    /// the first sign change of [`joule_thomson`](Self::joule_thomson); grid points CoolProp
+    /// cannot evaluate are skipped. The bracket is then refined by bisection until it is narrower
+    /// than 1e-6 K, and its midpoint is returned. On success the state is left at `(p, T_inv)`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if `p` is not finite and positive, and
+    /// [`Error::Computation`] if no sign change is found (for example above the maximum inversion
+    /// pressure). CoolProp errors while reading the temperature limits or evaluating a bisection
+    /// midpoint are propagated.
+    pub fn inversion_temperature(&mut self, p: f64) -> Result<f64> {
+        if !(p.is_finite() && p > 0.0) {
+            return Err(Error::InvalidInput(format!(
+                "pressure must be finite and positive, got {p}"
+            )));
+        }
+        let t_min = self.get(Param::TMin)?;
+        let t_max = self.get(Param::TMax)?;
+        let step = (t_max - t_min) / (INVERSION_SCAN_POINTS - 1) as f64;
+
+        let mut previous: Option<(f64, f64)> = None;
+        let mut bracket = None;
+        for i in (0..INVERSION_SCAN_POINTS).rev() {
+            let t = t_min + step * i as f64;
+            let Ok(mu) = self.joule_thomson_at(p, t) else {
+                continue;
+            };
+            if mu == 0.0 {
+                return Ok(t);
+            }
+            if let Some((t_hi, mu_hi)) = previous
+                && mu.signum() != mu_hi.signum()
+            {
+                bracket = Some((t, mu, t_hi));
+                break;
+            }
+            previous = Some((t, mu));
+        }
+        let Some((mut lo, mu_lo, mut hi)) = bracket else {
+            return Err(Error::Computation {
+                context: format!("AbstractState::inversion_temperature(p={p} Pa)"),
+                message: format!("no Joule-Thomson sign change between {t_min} K and {t_max} K"),
+            });
+        };
+
+        while hi - lo > INVERSION_T_TOL {
+            let mid = 0.5 * (lo + hi);
+            if self.joule_thomson_at(p, mid)?.signum() == mu_lo.signum() {
+                lo = mid;
+            } else {
+                hi = mid;
+            }
+        }
+        let t_inv = 0.5 * (lo + hi);
+        self.update(InputPair::PT, p, t_inv)?;
+        Ok(t_inv)
+    }
+
+    fn joule_thomson_at(&mut self, p: f64, t: f64) -> Result<f64> {
+        self.update(InputPair::PT, p, t)?;
+        let mu = self.joule_thomson()?;
+        if mu.is_finite() {
+            Ok(mu)
+        } else {
+            Err(Error::Computation {
+                context: format!("AbstractState::joule_thomson(p={p} Pa, T={t} K)"),
+                message: format!("non-finite coefficient {mu}"),
+            })
+        }
+    }
+
+    /// Reduced Helmholtz energy terms and their derivatives at the current state.
+    ///
+    /// The values of `alpha0`, `alphar`, their first derivatives, and `d2(alpha0)/d(delta)2` are
+    /// read directly from CoolProp. CoolProp does not expose the remaining second derivatives as
+    /// outputs, so they are recovered from the exact Helmholtz-energy identities
+    /// `cv0 = -R tau^2 alpha0_tautau`, `cv = -R tau^2 (alpha0_tautau + alphar_tautau)`,
+    /// `(dp/drho)_T = R T (1 + 2 delta alphar_delta + delta^2 alphar_deltadelta)`, and
+    /// `(dp/dT)_rho = rho R (1 + delta alphar_delta - delta tau alphar_deltatau)`, using molar
+    /// properties and the state's gas constant. See [`HelmholtzDerivs`] for the conventions.
+    ///
+    /// Requires a Helmholtz-energy backend such as `HEOS` and a single-phase state.
+    pub fn helmholtz_derivatives(&self) -> Result<HelmholtzDerivs> {
+        let r = self.get(Param::GasConstant)?;
+        let t = self.get(Param::T)?;
+        let rho = self.get(Param::Dmolar)?;
+        let tau = self.get(Param::Tau)?;
+        let delta = self.get(Param::Delta)?;
+        let dalphar_ddelta = self.get(Param::DalpharDdeltaConsttau)?;
+
+        let dp_drho_t = self.first_partial_deriv(Param::P, Param::Dmolar, Param::T)?;
+        let dp_dt_rho = self.first_partial_deriv(Param::P, Param::T, Param::Dmolar)?;
+        let d2alpha0_dtau2 = -(self.get(Param::Cp0molar)? - r) / (r * tau * tau);
+        let d2alphar_dtau2 = -self.get(Param::Cvmolar)? / (r * tau * tau) - d2alpha0_dtau2;
+        let d2alphar_ddelta2 =
+            (dp_drho_t / (r * t) - 1.0 - 2.0 * delta * dalphar_ddelta) / (delta * delta);
+        let d2alphar_ddelta_dtau =
+            (1.0 + delta * dalphar_ddelta - dp_dt_rho / (rho * r)) / (delta * tau);
+
+        Ok(HelmholtzDerivs {
+            tau,
+            delta,
+            alpha0: self.get(Param::Alpha0)?,
+            dalpha0_dtau: self.get(Param::Dalpha0DtauConstdelta)?,
+            dalpha0_ddelta: self.get(Param::Dalpha0DdeltaConsttau)?,
+            d2alpha0_dtau2,
+            d2alpha0_ddelta2: self.get(Param::D2Alpha0Ddelta2Consttau)?,
+            alphar: self.get(Param::Alphar)?,
+            dalphar_dtau: self.get(Param::DalpharDtauConstdelta)?,
+            dalphar_ddelta,
+            d2alphar_dtau2,
+            d2alphar_ddelta2,
+            d2alphar_ddelta_dtau,
+        })
+    }
+
     /// Second derivative along the saturation surface with mixed dependence.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if the current state is not two-phase.
     pub fn second_two_phase_deriv(
         &self,
         of1: Param,
@@ -390,6 +1361,7 @@ impl AbstractState {
         wrt2: Param,
         constant2: Param,
     ) -> Result<f64> {
+        self.require_two_phase("two-phase derivative")?;
         let of1 = self.indices.id_of_param(of1);
         let wrt1 = self.indices.id_of_param(wrt1);
         let constant1 = self.indices.id_of_param(constant1);
@@ -440,6 +1412,10 @@ impl AbstractState {
     }
 
     /// First two-phase derivative using CoolProp's spline interpolation scheme.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if the current state is not two-phase.
     pub fn first_two_phase_deriv_splined(
         &self,
         of: Param,
@@ -447,6 +1423,7 @@ impl AbstractState {
         constant: Param,
         x_end: f64,
     ) -> Result<f64> {
+        self.require_two_phase("two-phase derivative")?;
         let of = self.indices.id_of_param(of);
         let wrt = self.indices.id_of_param(wrt);
         let constant = self.indices.id_of_param(constant);
@@ -465,7 +1442,13 @@ impl AbstractState {
     }
 
     /// First derivative inside the two-phase region with analytical CoolProp routines.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if the current state is not two-phase, checked with
+    /// [`phase`](Self::phase) before calling into CoolProp, and otherwise any CoolProp error.
     pub fn first_two_phase_deriv(&self, of: Param, wrt: Param, constant: Param) -> Result<f64> {
+        self.require_two_phase("two-phase derivative")?;
         let of = self.indices.id_of_param(of);
         let wrt = self.indices.id_of_param(wrt);
         let constant = self.indices.id_of_param(constant);
@@ -482,10 +1465,28 @@ impl AbstractState {
         })
     }
 
+    fn require_two_phase(&self, what: &str) -> Result<()> {
+        match self.phase()? {
+            Phase::TwoPhase => Ok(()),
+            _ => Err(Error::InvalidInput(format!(
+                "{what} requires a two-phase state"
+            ))),
+        }
+    }
+
     /// Set molar composition fractions for mixtures.
     ///
     /// `fractions` must sum to one; CoolProp enforces additional backend-specific constraints.
+    /// The values are passed through unchanged, so whether a vector that does not sum to one is
+    /// rejected or silently renormalized depends on the backend. Use
+    /// [`set_fractions_normalized`](Self::set_fractions_normalized) to normalize explicitly.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] for an empty slice without calling CoolProp, and
+    /// otherwise propagates CoolProp's rejection of the fractions.
     pub fn set_fractions(&mut self, fractions: &[f64]) -> Result<()> {
+        require_components(fractions)?;
         let len = fractions.len() as c_long;
         call_with_error(|err, msg, buflen| unsafe {
             crate::ffi::AbstractState_set_fractions(
@@ -499,10 +1500,49 @@ impl AbstractState {
         })
     }
 
+    /// Set molar composition fractions after scaling them to sum to one.
+    ///
+    /// For example, `[0.3, 0.3]` is applied as `[0.5, 0.5]`. This gives the same composition on
+    /// every backend, unlike [`set_fractions`](Self::set_fractions), which passes values through
+    /// unchanged.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if any fraction is negative or non-finite, or if the
+    /// fractions sum to zero. CoolProp errors from setting the normalized fractions are
+    /// propagated.
+    pub fn set_fractions_normalized(&mut self, fractions: &[f64]) -> Result<()> {
+        let normalized = normalize_fractions(fractions)?;
+        self.set_fractions(&normalized)
+    }
+
+    /// Set molar composition fractions by component name.
+    ///
+    /// Keys are matched exactly against [`fluid_names_vec`](Self::fluid_names_vec) and the values
+    /// are reordered to CoolProp's component order before calling
+    /// [`set_fractions`](Self::set_fractions), so the result does not depend on the order in which
+    /// components were written in the fluid string.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if a key does not name a component of this fluid or a
+    /// component has no entry. CoolProp errors from setting the fractions are propagated.
+    pub fn set_fractions_named(&mut self, fractions: &HashMap<&str, f64>) -> Result<()> {
+        let components = self.fluid_names_vec()?;
+        let ordered = order_named_fractions(&components, fractions)?;
+        self.set_fractions(&ordered)
+    }
+
     /// Set mass composition fractions for mixtures.
     ///
     /// `fractions` must sum to one; interpretation is backend dependent.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] for an empty slice or when the CoolProp build lacks
+    /// `AbstractState_set_mass_fractions`, and otherwise propagates CoolProp errors.
     pub fn set_mass_fractions(&mut self, fractions: &[f64]) -> Result<()> {
+        require_components(fractions)?;
         #[cfg(coolprop_has_abstractstate_set_mass_fractions)]
         {
             let len = fractions.len() as c_long;
@@ -535,82 +1575,91 @@ impl AbstractState {
         Ok(count.max(1))
     }
 
+    /// Read a composition vector, growing the buffer until it holds every component.
+    ///
+    /// `fill` issues the FFI call for the given buffer and count out-parameter.
+    fn read_fractions(
+        &self,
+        mut fill: impl FnMut(&mut [f64], &mut c_long) -> Result<()>,
+    ) -> Result<Vec<f64>> {
+        grow_buffer(
+            BufferGrowth::new(self.estimated_component_capacity()?),
+            |capacity| {
+                let mut fractions = vec![0.0; capacity];
+                let mut count: c_long = 0;
+                match fill(&mut fractions, &mut count) {
+                    Ok(()) => {
+                        let actual = count.max(0) as usize;
+                        if actual > capacity {
+                            return Ok(Fill::Grow(actual));
+                        }
+                        fractions.truncate(actual);
+                        Ok(Fill::Done(fractions))
+                    }
+                    Err(err) => {
+                        let msg = err.to_string();
+                        if msg.contains("buffer") || msg.contains("Length of array") {
+                            Ok(Fill::Grow(0))
+                        } else {
+                            Err(err)
+                        }
+                    }
+                }
+            },
+        )
+    }
+
     /// Retrieve the current molar composition as a vector with automatic sizing.
+    ///
+    /// This is the overall (feed) composition set with [`set_fractions`](Self::set_fractions),
+    /// even in the two-phase region; see [`feed_mole_fractions`](Self::feed_mole_fractions).
     pub fn mole_fractions(&self) -> Result<Vec<f64>> {
-        let mut capacity = self.estimated_component_capacity()?;
-        loop {
-            let mut fractions = vec![0.0; capacity];
-            let mut count: c_long = 0;
-            match call_with_error(|err, msg, buflen| unsafe {
+        self.read_fractions(|fractions, count| {
+            call_with_error(|err, msg, buflen| unsafe {
                 crate::ffi::AbstractState_get_mole_fractions(
                     self.handle,
                     fractions.as_mut_ptr(),
-                    capacity as c_long,
-                    &mut count,
+                    fractions.len() as c_long,
+                    count,
                     err,
                     msg,
                     buflen,
                 );
-            }) {
-                Ok(()) => {
-                    let actual = count.max(0) as usize;
-                    if actual > capacity {
-                        capacity = actual.max(capacity * 2);
-                        continue;
-                    }
-                    fractions.truncate(actual);
-                    return Ok(fractions);
-                }
-                Err(err) => {
-                    let msg = err.to_string();
-                    if msg.contains("buffer") || msg.contains("Length of array") {
-                        capacity = capacity.max(1) * 2;
-                        continue;
-                    }
-                    return Err(err);
-                }
-            }
-        }
+            })
+        })
+    }
+
+    /// Overall (feed) molar composition of the mixture; an alias of
+    /// [`mole_fractions`](Self::mole_fractions).
+    ///
+    /// **This is not the composition of either phase.** In the two-phase region the feed splits
+    /// into a saturated liquid and a saturated vapor whose compositions generally differ from the
+    /// feed and from each other; read those with
+    /// [`mole_fractions_sat_state`](Self::mole_fractions_sat_state). The feed composition is
+    /// unchanged by [`update`](Self::update) and stays a quality-weighted average of the two phase
+    /// compositions.
+    #[inline]
+    pub fn feed_mole_fractions(&self) -> Result<Vec<f64>> {
+        self.mole_fractions()
     }
 
     /// Retrieve the current mass composition as a vector with automatic sizing.
     pub fn mass_fractions(&self) -> Result<Vec<f64>> {
         #[cfg(coolprop_has_abstractstate_get_mass_fractions)]
         {
-            let mut capacity = self.estimated_component_capacity()?;
-            loop {
-                let mut fractions = vec![0.0; capacity];
-                let mut count: c_long = 0;
-                match call_with_error(|err, msg, buflen| unsafe {
+            self.read_fractions(|fractions, count| {
+                call_with_error(|err, msg, buflen| unsafe {
                     crate::ffi::AbstractState_get_mass_fractions(
                         self.handle,
                         fractions.as_mut_ptr(),
-                        capacity as c_long,
-                        &mut count,
+                        fractions.len() as c_long,
+                        count,
                         err,
                         msg,
                         buflen,
                     );
-                }) {
-                    Ok(()) => {
-                        let actual = count.max(0) as usize;
-                        if actual > capacity {
-                            capacity = actual.max(capacity * 2);
-                            continue;
-                        }
-                        fractions.truncate(actual);
-                        return Ok(fractions);
-                    }
-                    Err(err) => {
-                        let msg = err.to_string();
-                        if msg.contains("buffer") || msg.contains("Length of array") {
-                            capacity = capacity.max(1) * 2;
-                            continue;
-                        }
-                        return Err(err);
-                    }
-                }
-            }
+                })
+            })
         }
         #[cfg(not(coolprop_has_abstractstate_get_mass_fractions))]
         {
@@ -621,6 +1670,9 @@ impl AbstractState {
     }
 
     /// Retrieve saturation compositions for the specified phase (`liquid` or `gas`).
+    ///
+    /// These are the per-phase compositions of a two-phase state, as opposed to the overall feed
+    /// composition returned by [`feed_mole_fractions`](Self::feed_mole_fractions).
     pub fn mole_fractions_sat_state(&self, phase: Phase) -> Result<Vec<f64>> {
         let token = phase.saturation_token().ok_or_else(|| {
             Error::InvalidInput(format!(
@@ -631,52 +1683,133 @@ impl AbstractState {
             label: "phase",
             source,
         })?;
-        let mut capacity = self.estimated_component_capacity()?;
-        loop {
-            let mut fractions = vec![0.0; capacity];
-            let mut count: c_long = 0;
-            match call_with_error(|err, msg, buflen| unsafe {
+        self.read_fractions(|fractions, count| {
+            call_with_error(|err, msg, buflen| unsafe {
                 crate::ffi::AbstractState_get_mole_fractions_satState(
                     self.handle,
                     phase.as_ptr(),
                     fractions.as_mut_ptr(),
-                    capacity as c_long,
-                    &mut count,
+                    fractions.len() as c_long,
+                    count,
                     err,
                     msg,
                     buflen,
                 );
-            }) {
-                Ok(()) => {
-                    let actual = count.max(0) as usize;
-                    if actual > capacity {
-                        capacity = actual.max(capacity * 2);
-                        continue;
-                    }
-                    fractions.truncate(actual);
-                    return Ok(fractions);
-                }
-                Err(err) => {
-                    let msg = err.to_string();
-                    if msg.contains("buffer") || msg.contains("Length of array") {
-                        capacity = capacity.max(1) * 2;
-                        continue;
-                    }
-                    return Err(err);
+            })
+        })
+    }
+
+    /// Like [`mole_fractions_sat_state`](Self::mole_fractions_sat_state), but taking CoolProp's
+    /// integer phase code (`0` for liquid, `5` for gas) so generic code can loop over phases.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::UnknownPhaseCode`] if `code` is not a CoolProp phase,
+    /// [`Error::InvalidInput`] if it names any phase other than liquid or gas, and otherwise the
+    /// errors of [`mole_fractions_sat_state`](Self::mole_fractions_sat_state).
+    pub fn mole_fractions_for_phase_code(&self, code: i32) -> Result<Vec<f64>> {
+        let phase = Phase::from_code(code).ok_or(Error::UnknownPhaseCode(code as i64))?;
+        if !matches!(phase, Phase::Liquid | Phase::Gas) {
+            return Err(Error::InvalidInput(format!(
+                "phase code {code} ({phase}) has no saturation composition; use 0 (liquid) or 5 (gas)"
+            )));
+        }
+        self.mole_fractions_sat_state(phase)
+    }
+
+    /// Equilibrium ratios `K_i = y_i / x_i` of a two-phase state, in component order.
+    ///
+    /// `y` and `x` are the vapor and liquid compositions from
+    /// [`mole_fractions_sat_state`](Self::mole_fractions_sat_state). At equilibrium these equal
+    /// the ratios of the liquid to vapor fugacity coefficients.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if the state is not two-phase, and [`Error::Computation`]
+    /// if a liquid fraction is below `1e-12` (for example a non-condensable component), where the
+    /// ratio is not meaningful. CoolProp errors are propagated.
+    pub fn k_values(&self) -> Result<Vec<f64>> {
+        self.require_two_phase("K-values")?;
+        let liquid = self.mole_fractions_sat_state(Phase::Liquid)?;
+        let vapor = self.mole_fractions_sat_state(Phase::Gas)?;
+        if liquid.len() != vapor.len() {
+            return Err(Error::Computation {
+                context: "k_values".into(),
+                message: format!(
+                    "{} liquid fractions but {} vapor fractions",
+                    liquid.len(),
+                    vapor.len()
+                ),
+            });
+        }
+        liquid
+            .iter()
+            .zip(&vapor)
+            .enumerate()
+            .map(|(i, (&x, &y))| {
+                if x < MIN_LIQUID_FRACTION {
+                    Err(Error::Computation {
+                        context: "k_values".into(),
+                        message: format!("liquid mole fraction of component {i} is {x}"),
+                    })
+                } else {
+                    Ok(y / x)
                 }
-            }
+            })
+            .collect()
+    }
+
+    /// Excess molar enthalpy of the mixture at its current temperature and pressure, in J/mol.
+    ///
+    /// Computed as the mixture `Hmolar` minus the mole-fraction-weighted `Hmolar` of each pure
+    /// component, where every component is evaluated in a fresh state of the same backend at the
+    /// current `(T, p)`. Each pure component takes its own stable phase at those conditions, so
+    /// the result includes any latent heat between a component's phase and the mixture's. For a
+    /// pure fluid the result is zero.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if the component names cannot be matched to the mole
+    /// fractions, and propagates CoolProp errors from the mixture or any pure-component state
+    /// (for example when `(T, p)` lies exactly on a component's saturation curve).
+    pub fn excess_enthalpy(&self) -> Result<f64> {
+        let t = self.get(Param::T)?;
+        let p = self.get(Param::P)?;
+        let h_mixture = self.get(Param::Hmolar)?;
+        let fractions = self.mole_fractions()?;
+        let backend = self.backend_name()?;
+        let components = self.fluid_names_vec()?;
+        if components.len() != fractions.len() {
+            return Err(Error::InvalidInput(format!(
+                "cannot match components {components:?} to {} mole fractions",
+                fractions.len()
+            )));
+        }
+
+        let mut h_ideal = 0.0;
+        for (name, x) in components.into_iter().zip(fractions) {
+            let mut pure = Self::new(&backend, &name)?;
+            pure.update(InputPair::PT, p, t)?;
+            h_ideal += x * pure.get(Param::Hmolar)?;
         }
+        Ok(h_mixture - h_ideal)
     }
 
     /// Component fugacity in pascals.
-    pub fn get_fugacity(&self, i: c_long) -> Result<f64> {
+    ///
+    /// `i` is a raw `c_long` or a checked [`ComponentIndex`].
+    pub fn get_fugacity(&self, i: impl Into<c_long>) -> Result<f64> {
+        let i = i.into();
         call_with_error(|err, msg, len| unsafe {
             crate::ffi::AbstractState_get_fugacity(self.handle, i, err, msg, len)
         })
     }
 
     /// Component fugacity coefficient (dimensionless).
-    pub fn get_fugacity_coefficient(&self, i: c_long) -> Result<f64> {
+    ///
+    /// `i` is a raw `c_long` or a checked [`ComponentIndex`].
+    pub fn get_fugacity_coefficient(&self, i: impl Into<c_long>) -> Result<f64> {
+        let i = i.into();
         call_with_error(|err, msg, len| unsafe {
             crate::ffi::AbstractState_get_fugacity_coefficient(self.handle, i, err, msg, len)
         })
@@ -686,18 +1819,31 @@ impl AbstractState {
     ///
     /// Returns temperature, pressure, molar density, molar enthalpy, and molar entropy arrays in
     /// a single struct. The returned vectors always match the length of the input slices.
+    ///
+    /// Empty inputs return empty vectors without calling into CoolProp. The whole batch is
+    /// evaluated in one call and all outputs are allocated up front, 40 bytes per state here;
+    /// split very large batches if that matters.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if the slices differ in length or hold more states than a
+    /// C `long` can count, and otherwise the CoolProp error for the batch.
     pub fn update_and_common_out(
         &mut self,
         pair: InputPair,
         value1: &[f64],
         value2: &[f64],
     ) -> Result<BatchCommonOutputs> {
-        if value1.len() != value2.len() {
-            return Err(Error::InvalidInput(
-                "value arrays must be the same length".into(),
-            ));
+        let len = batch_len(value1, value2)?;
+        if len == 0 {
+            return Ok(BatchCommonOutputs {
+                temperature: Vec::new(),
+                pressure: Vec::new(),
+                rhomolar: Vec::new(),
+                hmolar: Vec::new(),
+                smolar: Vec::new(),
+            });
         }
-        let len = value1.len();
         let mut temperature = vec![0.0; len];
         let mut pressure = vec![0.0; len];
         let mut rhomolar = vec![0.0; len];
@@ -721,6 +1867,7 @@ impl AbstractState {
                 buflen,
             );
         })?;
+        self.updated = true;
         Ok(BatchCommonOutputs {
             temperature,
             pressure,
@@ -731,6 +1878,9 @@ impl AbstractState {
     }
 
     /// Batched update returning a single additional property as an owned vector.
+    ///
+    /// Empty inputs return an empty vector without calling into CoolProp. Errors are the same as
+    /// for [`update_and_common_out`](Self::update_and_common_out).
     pub fn update_and_1_out(
         &mut self,
         pair: InputPair,
@@ -738,12 +1888,10 @@ impl AbstractState {
         value2: &[f64],
         output: Param,
     ) -> Result<Vec<f64>> {
-        if value1.len() != value2.len() {
-            return Err(Error::InvalidInput(
-                "value arrays must be the same length".into(),
-            ));
+        let len = batch_len(value1, value2)?;
+        if len == 0 {
+            return Ok(Vec::new());
         }
-        let len = value1.len();
         let mut out = vec![0.0; len];
         let id = self.indices.id_of_pair(pair);
         let out_param = self.indices.id_of_param(output);
@@ -761,10 +1909,14 @@ impl AbstractState {
                 buflen,
             );
         })?;
+        self.updated = true;
         Ok(out)
     }
 
     /// Batched update returning five arbitrary properties as owned vectors.
+    ///
+    /// Empty inputs return empty vectors without calling into CoolProp. Errors are the same as
+    /// for [`update_and_common_out`](Self::update_and_common_out).
     pub fn update_and_5_out(
         &mut self,
         pair: InputPair,
@@ -772,12 +1924,10 @@ impl AbstractState {
         value2: &[f64],
         outputs: [Param; 5],
     ) -> Result<[Vec<f64>; 5]> {
-        if value1.len() != value2.len() {
-            return Err(Error::InvalidInput(
-                "value arrays must be the same length".into(),
-            ));
+        let len = batch_len(value1, value2)?;
+        if len == 0 {
+            return Ok(Default::default());
         }
-        let len = value1.len();
         let mut out1 = vec![0.0; len];
         let mut out2 = vec![0.0; len];
         let mut out3 = vec![0.0; len];
@@ -803,17 +1953,39 @@ impl AbstractState {
                 buflen,
             );
         })?;
+        self.updated = true;
         Ok([out1, out2, out3, out4, out5])
     }
 
+    /// Evaluate `output` across the saturation dome at pressure `p` (Pa), one value per quality.
+    ///
+    /// The state is updated with [`InputPair::PQ`] for each entry of `qualities` in turn and is
+    /// left at the last one. Sweeping `q` from 0 to 1 with `Hmass` or `Smass` as the output
+    /// traces the isobar across a P-h or T-s diagram's two-phase region.
+    ///
+    /// # Errors
+    ///
+    /// Stops at the first failing update or output, for example a quality outside `[0, 1]` or a
+    /// pressure above the critical pressure, and returns that error.
+    pub fn quality_sweep(&mut self, p: f64, qualities: &[f64], output: Param) -> Result<Vec<f64>> {
+        qualities
+            .iter()
+            .map(|&q| {
+                self.update(InputPair::PQ, p, q)?;
+                self.get(output)
+            })
+            .collect()
+    }
+
     /// Override binary interaction parameters for mixture models.
     ///
-    /// Arguments `i` and `j` index the components, `parameter` is the CoolProp keyword, and
-    /// `value` is supplied in backend-specific units.
+    /// Arguments `i` and `j` index the components (raw `c_long`s or checked
+    /// [`ComponentIndex`]es), `parameter` is the CoolProp keyword, and `value` is supplied in
+    /// backend-specific units.
     pub fn set_binary_interaction_double(
         &mut self,
-        i: c_long,
-        j: c_long,
+        i: impl Into<c_long>,
+        j: impl Into<c_long>,
         parameter: &str,
         value: f64,
     ) -> Result<()> {
@@ -821,24 +1993,67 @@ impl AbstractState {
             label: "parameter",
             source,
         })?;
-        call_with_error(|err, msg, len| unsafe {
-            crate::ffi::AbstractState_set_binary_interaction_double(
-                self.handle,
-                i,
-                j,
-                parameter.as_ptr(),
-                value,
-                err,
-                msg,
-                len,
-            );
+        self.apply_model_override(ModelOverride::BinaryInteraction {
+            i: i.into(),
+            j: j.into(),
+            parameter,
+            value,
         })
     }
 
+    /// Read back a binary interaction parameter, such as the `kij` in use for a component pair.
+    ///
+    /// Arguments mirror [`set_binary_interaction_double`](Self::set_binary_interaction_double):
+    /// `i` and `j` index the components and `parameter` is the CoolProp keyword. This reports
+    /// CoolProp's default for the pair unless it was overridden on this state.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::CoolProp`] with CoolProp's message if the backend does not know
+    /// `parameter` or the indices, [`Error::EmbeddedNul`] if `parameter` contains a NUL byte, and
+    /// [`Error::InvalidInput`] if the CoolProp build lacks
+    /// `AbstractState_get_binary_interaction_double`.
+    pub fn get_binary_interaction_double(
+        &self,
+        i: impl Into<c_long>,
+        j: impl Into<c_long>,
+        parameter: &str,
+    ) -> Result<f64> {
+        let (i, j) = (i.into(), j.into());
+        let parameter = CString::new(parameter).map_err(|source| Error::EmbeddedNul {
+            label: "parameter",
+            source,
+        })?;
+        #[cfg(coolprop_has_abstractstate_get_binary_interaction_double)]
+        {
+            call_with_error(|err, msg, len| unsafe {
+                crate::ffi::AbstractState_get_binary_interaction_double(
+                    self.handle,
+                    i,
+                    j,
+                    parameter.as_ptr(),
+                    err,
+                    msg,
+                    len,
+                )
+            })
+        }
+        #[cfg(not(coolprop_has_abstractstate_get_binary_interaction_double))]
+        {
+            let _ = (i, j, parameter);
+            Err(Error::InvalidInput(
+                "this CoolProp build does not expose AbstractState_get_binary_interaction_double"
+                    .into(),
+            ))
+        }
+    }
+
     /// Set custom coefficients for cubic equation-of-state alpha functions.
+    ///
+    /// `i` is a raw `c_long` or a checked [`ComponentIndex`].
     pub fn set_cubic_alpha_c(
         &mut self,
-        i: c_long,
+        i: impl Into<c_long>,
         parameter: &str,
         c1: f64,
         c2: f64,
@@ -848,25 +2063,19 @@ impl AbstractState {
             label: "parameter",
             source,
         })?;
-        call_with_error(|err, msg, len| unsafe {
-            crate::ffi::AbstractState_set_cubic_alpha_C(
-                self.handle,
-                i,
-                parameter.as_ptr(),
-                c1,
-                c2,
-                c3,
-                err,
-                msg,
-                len,
-            );
+        self.apply_model_override(ModelOverride::CubicAlphaC {
+            i: i.into(),
+            parameter,
+            c: [c1, c2, c3],
         })
     }
 
     /// Override a scalar fluid parameter on a per-component basis.
+    ///
+    /// `i` is a raw `c_long` or a checked [`ComponentIndex`].
     pub fn set_fluid_parameter_double(
         &mut self,
-        i: c_long,
+        i: impl Into<c_long>,
         parameter: &str,
         value: f64,
     ) -> Result<()> {
@@ -874,19 +2083,67 @@ impl AbstractState {
             label: "parameter",
             source,
         })?;
-        call_with_error(|err, msg, len| unsafe {
-            crate::ffi::AbstractState_set_fluid_parameter_double(
-                self.handle,
-                i,
-                parameter.as_ptr(),
-                value,
-                err,
-                msg,
-                len,
-            );
+        self.apply_model_override(ModelOverride::FluidParameter {
+            i: i.into(),
+            parameter,
+            value,
         })
     }
 
+    /// Pass `model_override` to CoolProp and record it for [`model_clone`](Self::model_clone)
+    /// once it is accepted.
+    fn apply_model_override(&mut self, model_override: ModelOverride) -> Result<()> {
+        call_with_error(|err, msg, len| unsafe {
+            match &model_override {
+                ModelOverride::BinaryInteraction {
+                    i,
+                    j,
+                    parameter,
+                    value,
+                } => crate::ffi::AbstractState_set_binary_interaction_double(
+                    self.handle,
+                    *i,
+                    *j,
+                    parameter.as_ptr(),
+                    *value,
+                    err,
+                    msg,
+                    len,
+                ),
+                ModelOverride::CubicAlphaC {
+                    i,
+                    parameter,
+                    c: [c1, c2, c3],
+                } => crate::ffi::AbstractState_set_cubic_alpha_C(
+                    self.handle,
+                    *i,
+                    parameter.as_ptr(),
+                    *c1,
+                    *c2,
+                    *c3,
+                    err,
+                    msg,
+                    len,
+                ),
+                ModelOverride::FluidParameter {
+                    i,
+                    parameter,
+                    value,
+                } => crate::ffi::AbstractState_set_fluid_parameter_double(
+                    self.handle,
+                    *i,
+                    parameter.as_ptr(),
+                    *value,
+                    err,
+                    msg,
+                    len,
+                ),
+            }
+        })?;
+        self.model_overrides.push(model_override);
+        Ok(())
+    }
+
     /// Trigger CoolProp's phase-envelope construction for the current mixture.
     ///
     /// `level` controls the resolution/detail as understood by CoolProp.
@@ -961,7 +2218,7 @@ impl AbstractState {
             components_guess = 1;
         }
 
-        loop {
+        grow_buffer(BufferGrowth::new(points_guess), |points_guess| {
             let mut temperature = vec![0.0; points_guess];
             let mut pressure = vec![0.0; points_guess];
             let mut rhomolar_vap = vec![0.0; points_guess];
@@ -994,9 +2251,8 @@ impl AbstractState {
                 Err(err) => {
                     let msg = err.to_string();
                     if msg.contains("buffer") || msg.contains("length") {
-                        points_guess = points_guess.max(1) * 2;
                         components_guess = components_guess.max(1) * 2;
-                        continue;
+                        return Ok(Fill::Grow(0));
                     }
                     return Err(err);
                 }
@@ -1005,9 +2261,8 @@ impl AbstractState {
             let actual_points = reported_length.max(0) as usize;
             let actual_components = reported_components.max(0) as usize;
             if actual_points > points_guess || actual_components > components_guess {
-                points_guess = points_guess.max(actual_points).max(1) * 2;
                 components_guess = components_guess.max(actual_components).max(1);
-                continue;
+                return Ok(Fill::Grow(actual_points));
             }
 
             temperature.truncate(actual_points);
@@ -1037,15 +2292,30 @@ impl AbstractState {
                 reshape_phase_compositions(&y_flat, actual_points, actual_components)
             };
 
-            return Ok(PhaseEnvelope {
+            Ok(Fill::Done(PhaseEnvelope {
                 temperature,
                 pressure,
                 rhomolar_liq,
                 rhomolar_vap,
                 x: x_matrix,
                 y: y_matrix,
-            });
-        }
+            }))
+        })
+    }
+
+    /// Whether the current mixture condenses retrogradely at pressure `p` (Pa).
+    ///
+    /// Builds the phase envelope, isolates the dew branch (points where the phase carrying the
+    /// feed composition is the less dense one, as in [`PhaseEnvelope::at_pressure`]), and
+    /// returns `true` when a dew-branch segment bracketing `p` has a negative slope `dT/dP`.
+    /// That is the region between the cricondentherm and the cricondenbar, where lowering the
+    /// pressure at constant temperature condenses liquid. Returns `false` when the dew branch
+    /// does not reach `p`.
+    pub fn is_retrograde(&mut self, p: f64) -> Result<bool> {
+        let feed = self.mole_fractions()?;
+        self.build_phase_envelope("none")?;
+        let envelope = self.phase_envelope()?;
+        Ok(dew_slope_negative_at(&envelope, &feed, p))
     }
 
     /// Build the spinodal curve for the current mixture.
@@ -1159,11 +2429,282 @@ impl fmt::Debug for AbstractState {
             .field("handle", &self.handle)
             .field("backend", &backend)
             .field("fluids", &fluids)
+            .field(
+                "last_update",
+                &self
+                    .last_update
+                    .map(|(pair, v1, v2)| describe_update(pair, v1, v2)),
+            )
             .finish()
     }
 }
 
-fn call_with_error<R>(f: impl FnOnce(*mut c_long, *mut c_char, c_long) -> R) -> Result<R> {
+/// Build phase envelopes for a mixture at each of the supplied compositions.
+///
+/// Every entry of `compositions` is a mole-fraction vector passed to
+/// [`AbstractState::set_fractions`] before the envelope is built. Results preserve the input
+/// order. With the `rayon` feature enabled the envelopes are built in parallel, using one
+/// `AbstractState` per worker; otherwise a single state is reused sequentially.
+///
+/// # Errors
+///
+/// Returns the first error raised while constructing a state, setting a composition, or
+/// building an envelope.
+pub fn phase_envelopes_over_compositions(
+    backend: &str,
+    fluid: &str,
+    compositions: &[Vec<f64>],
+) -> Result<Vec<PhaseEnvelope>> {
+    #[cfg(feature = "rayon")]
+    {
+        use rayon::prelude::*;
+
+        compositions
+            .par_iter()
+            .map_init(
+                || None,
+                |slot: &mut Option<AbstractState>, fractions| {
+                    if slot.is_none() {
+                        *slot = Some(AbstractState::new(backend, fluid)?);
+                    }
+                    let state = slot.as_mut().expect("worker state initialized");
+                    envelope_at_composition(state, fractions)
+                },
+            )
+            .collect()
+    }
+    #[cfg(not(feature = "rayon"))]
+    {
+        let mut state = AbstractState::new(backend, fluid)?;
+        compositions
+            .iter()
+            .map(|fractions| envelope_at_composition(&mut state, fractions))
+            .collect()
+    }
+}
+
+fn envelope_at_composition(state: &mut AbstractState, fractions: &[f64]) -> Result<PhaseEnvelope> {
+    state.set_fractions(fractions)?;
+    state.build_phase_envelope("none")?;
+    state.phase_envelope()
+}
+
+/// Trace the critical locus of a binary mixture by sweeping its composition.
+///
+/// The mole fraction `x` of the first component takes `n` evenly spaced values strictly between
+/// 0 and 1, `(i + 1) / (n + 1)`, since the pure-fluid ends are better served by the fluids'
+/// own critical constants. At each composition the first stable point reported by
+/// [`AbstractState::critical_points`] is kept and paired with `x`. Compositions at which
+/// CoolProp fails or finds no stable critical point are skipped, so the locus may hold fewer
+/// than `n` entries; the output stays in increasing `x` order.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if `n` is zero, propagates errors from constructing the
+/// mixture state, and returns [`Error::Computation`] if no composition yields a critical point.
+pub fn critical_locus(
+    backend: &str,
+    components: (&str, &str),
+    n: usize,
+) -> Result<Vec<(f64, CriticalPoint)>> {
+    if n == 0 {
+        return Err(Error::InvalidInput(
+            "a critical locus needs at least 1 composition".into(),
+        ));
+    }
+    let (first, second) = components;
+    let mut state = AbstractState::new(backend, &format!("{first}&{second}"))?;
+    let locus: Vec<_> = (0..n)
+        .filter_map(|i| {
+            let x = (i + 1) as f64 / (n + 1) as f64;
+            state.set_fractions(&[x, 1.0 - x]).ok()?;
+            let point = state
+                .critical_points()
+                .ok()?
+                .into_iter()
+                .find(|point| point.stable)?;
+            Some((x, point))
+        })
+        .collect();
+    if locus.is_empty() {
+        return Err(Error::Computation {
+            context: format!("critical_locus({first}&{second})"),
+            message: format!("no stable critical point found at any of {n} compositions"),
+        });
+    }
+    Ok(locus)
+}
+
+/// Retrieve state-independent constants for many fluids as a `fluids × outputs` table.
+///
+/// One `AbstractState` is constructed per fluid and reused for all `outputs`, which avoids
+/// re-parsing the fluid for every entry as repeated [`props1_si`](crate::props1_si) calls would.
+/// Fluids may carry a backend prefix such as `"INCOMP::MEG-50%"`; `HEOS` is used otherwise.
+/// Entries are `None` when the fluid does not have the constant: CoolProp reports it as not
+/// implemented or not available for that backend (for example a critical temperature for an
+/// incompressible), or returns a non-finite value.
+///
+/// # Errors
+///
+/// Returns an error if any fluid cannot be loaded, for example an unknown fluid name, and any
+/// other error from reading an entry, such as [`Error::InvalidInput`] for a state-dependent
+/// output (see [`Param::is_trivial`]).
+pub fn fluid_constants_table(fluids: &[&str], outputs: &[Param]) -> Result<Vec<Vec<Option<f64>>>> {
+    fluids
+        .iter()
+        .map(|fluid| {
+            let state = state_for_fluid(fluid)?;
+            outputs
+                .iter()
+                .map(|&param| match state.get(param) {
+                    Ok(value) => Ok(value.is_finite().then_some(value)),
+                    Err(err) if is_missing_constant(&err) => Ok(None),
+                    Err(err) => Err(err),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// Whether `err` is CoolProp reporting that a backend has no value for a constant at all, as
+/// opposed to failing to compute one.
+fn is_missing_constant(err: &Error) -> bool {
+    let Error::CoolProp { message, .. } = err else {
+        return false;
+    };
+    let message = message.to_ascii_lowercase();
+    ["not implemented", "not available", "not defined"]
+        .iter()
+        .any(|needle| message.contains(needle))
+}
+
+/// Evaluate `output` over a temperature × pressure grid, as `grid[i][j]` at `(t[i], p[j])`.
+///
+/// A single `AbstractState` is reused for the whole grid. Points where the update or the output
+/// fails, or yields a non-finite value, are stored as `NaN` instead of aborting the grid, which
+/// suits contour plots that should simply leave such cells blank. `fluid` accepts an optional
+/// `BACKEND::` prefix and defaults to `HEOS`.
+///
+/// # Errors
+///
+/// Returns an error only if the fluid cannot be loaded.
+pub fn property_grid(fluid: &str, output: Param, t: &[f64], p: &[f64]) -> Result<Vec<Vec<f64>>> {
+    let mut state = state_for_fluid(fluid)?;
+    Ok(t.iter()
+        .map(|&t| {
+            p.iter()
+                .map(|&p| {
+                    state
+                        .update(InputPair::PT, p, t)
+                        .and_then(|()| state.get_checked(output))
+                        .unwrap_or(f64::NAN)
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Classify the phase over a temperature × pressure grid, as `grid[i][j]` at `(t[i], p[j])`.
+///
+/// The phase-region analogue of [`property_grid`]: one `AbstractState` is reused, and points
+/// where the update or the phase lookup fails are stored as [`Phase::Unknown`].
+///
+/// # Errors
+///
+/// Returns an error only if the fluid cannot be loaded.
+pub fn phase_grid(fluid: &str, t: &[f64], p: &[f64]) -> Result<Vec<Vec<Phase>>> {
+    let mut state = state_for_fluid(fluid)?;
+    Ok(t.iter()
+        .map(|&t| {
+            p.iter()
+                .map(|&p| {
+                    state
+                        .update(InputPair::PT, p, t)
+                        .and_then(|()| state.phase())
+                        .unwrap_or(Phase::Unknown)
+                })
+                .collect()
+        })
+        .collect())
+}
+
+/// Mass-specific enthalpy difference `h2 - h1` between two states of the same fluid, in J/kg.
+///
+/// Each state is given as the `(pair, v1, v2)` arguments of [`AbstractState::update`], and a
+/// single `AbstractState` is reused for both. Multiply by a mass flow rate in kg/s to obtain a
+/// heat duty in watts. `fluid` accepts an optional `BACKEND::` prefix and defaults to `HEOS`.
+///
+/// # Errors
+///
+/// Propagates errors from loading the fluid or updating either state.
+pub fn enthalpy_difference(
+    fluid: &str,
+    state1: (InputPair, f64, f64),
+    state2: (InputPair, f64, f64),
+) -> Result<f64> {
+    let mut state = state_for_fluid(fluid)?;
+    let (pair, v1, v2) = state1;
+    state.update(pair, v1, v2)?;
+    let h1 = state.get(Param::Hmass)?;
+    let (pair, v1, v2) = state2;
+    state.update(pair, v1, v2)?;
+    let h2 = state.get(Param::Hmass)?;
+    Ok(h2 - h1)
+}
+
+/// Specific entropy generation of a steady-flow process between two states, in J/(kg*K).
+///
+/// Computes `s_gen = (s_out - s_in) - q_in / t_surroundings` on a mass basis, where `q_in` is the
+/// heat added to the fluid per unit mass (J/kg, negative for heat rejected) and exchanged with
+/// surroundings at `t_surroundings` (K). Each state is given as the `(pair, v1, v2)` arguments
+/// of [`AbstractState::update`] and a single `AbstractState` is reused, as in
+/// [`enthalpy_difference`]. The second law requires `s_gen >= 0`; a negative result points to
+/// inconsistent inputs such as a heat sign error.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if `t_surroundings` is not finite and positive, and
+/// propagates errors from loading the fluid or updating either state.
+pub fn entropy_generation(
+    fluid: &str,
+    inlet: (InputPair, f64, f64),
+    outlet: (InputPair, f64, f64),
+    t_surroundings: f64,
+    q_in: f64,
+) -> Result<f64> {
+    if !(t_surroundings.is_finite() && t_surroundings > 0.0) {
+        return Err(Error::InvalidInput(format!(
+            "surroundings temperature must be finite and positive, got {t_surroundings}"
+        )));
+    }
+    let mut state = state_for_fluid(fluid)?;
+    let (pair, v1, v2) = inlet;
+    state.update(pair, v1, v2)?;
+    let s_in = state.get(Param::Smass)?;
+    let (pair, v1, v2) = outlet;
+    state.update(pair, v1, v2)?;
+    let s_out = state.get(Param::Smass)?;
+    Ok(s_out - s_in - q_in / t_surroundings)
+}
+
+/// Construct a state from a fluid string with an optional `BACKEND::` prefix (default `HEOS`).
+pub(crate) fn state_for_fluid(fluid: &str) -> Result<AbstractState> {
+    match fluid.split_once("::") {
+        Some((backend, name)) => AbstractState::new(backend, name),
+        None => AbstractState::new("HEOS", fluid),
+    }
+}
+
+pub(crate) fn call_with_error<R>(
+    f: impl FnOnce(*mut c_long, *mut c_char, c_long) -> R,
+) -> Result<R> {
+    // The closure's type name carries the path of the wrapper that issued the call.
+    #[cfg(feature = "log")]
+    let (context, started) = {
+        let context = ffi_call_context(std::any::type_name_of_val(&f));
+        log::trace!("CoolProp FFI enter: {context}");
+        (context, std::time::Instant::now())
+    };
     let mut err: c_long = 0;
     let mut buf = [0 as c_char; ERR_BUF_LEN];
     let result = f(
@@ -1171,6 +2712,11 @@ fn call_with_error<R>(f: impl FnOnce(*mut c_long, *mut c_char, c_long) -> R) ->
         buf.as_mut_ptr(),
         ERR_BUF_LEN as c_long,
     );
+    #[cfg(feature = "log")]
+    log::trace!(
+        "CoolProp FFI exit: {context} (code {err}, {:?})",
+        started.elapsed()
+    );
     if err != 0 {
         // Protect against non-terminated writes from the C side.
         buf[ERR_BUF_LEN - 1] = 0;
@@ -1183,6 +2729,160 @@ fn call_with_error<R>(f: impl FnOnce(*mut c_long, *mut c_char, c_long) -> R) ->
     Ok(result)
 }
 
+#[cfg(feature = "log")]
+fn ffi_call_context(closure_type: &str) -> &str {
+    // Calls issued from inside a retry helper are nested one closure deeper.
+    let mut path = closure_type;
+    while let Some(outer) = path.strip_suffix("::{{closure}}") {
+        path = outer;
+    }
+    path.strip_prefix("coolprop::abstract_state::")
+        .unwrap_or(path)
+}
+
+fn order_named_fractions(components: &[String], named: &HashMap<&str, f64>) -> Result<Vec<f64>> {
+    if let Some(unknown) = named
+        .keys()
+        .find(|key| !components.iter().any(|name| name == *key))
+    {
+        return Err(Error::InvalidInput(format!(
+            "`{unknown}` is not a component of {components:?}"
+        )));
+    }
+    components
+        .iter()
+        .map(|name| {
+            named.get(name.as_str()).copied().ok_or_else(|| {
+                Error::InvalidInput(format!("missing mole fraction for component `{name}`"))
+            })
+        })
+        .collect()
+}
+
+fn normalize_fractions(fractions: &[f64]) -> Result<Vec<f64>> {
+    if let Some(bad) = fractions.iter().find(|x| !(x.is_finite() && **x >= 0.0)) {
+        return Err(Error::InvalidInput(format!(
+            "mole fractions must be finite and non-negative, got {bad}"
+        )));
+    }
+    let total: f64 = fractions.iter().sum();
+    if total <= 0.0 {
+        return Err(Error::InvalidInput(
+            "mole fractions must not all be zero".into(),
+        ));
+    }
+    Ok(fractions.iter().map(|x| x / total).collect())
+}
+
+#[cfg(any(test, all(feature = "log", debug_assertions)))]
+fn density_is_implausible(density: f64, rho_reducing: f64) -> bool {
+    rho_reducing.is_finite() && rho_reducing > 0.0 && density > MAX_REDUCED_DENSITY * rho_reducing
+}
+
+/// Whether `dmolar` (mol/m^3) is below liquid densities taken as molar, but within the liquid
+/// range taken as a mass density (kg/m^3), given the molar reducing density and molar mass.
+#[cfg(any(test, all(feature = "log", debug_assertions)))]
+fn molar_density_reads_as_mass(dmolar: f64, rhomolar_reducing: f64, molar_mass: f64) -> bool {
+    // Non-finite or non-positive inputs give reduced densities outside the liquid range.
+    let reduced_as_molar = dmolar / rhomolar_reducing;
+    let reduced_as_mass = dmolar / (rhomolar_reducing * molar_mass);
+    reduced_as_molar < MIN_LIQUID_REDUCED_DENSITY
+        && (MIN_LIQUID_REDUCED_DENSITY..=MAX_REDUCED_DENSITY).contains(&reduced_as_mass)
+}
+
+fn describe_update(pair: InputPair, v1: f64, v2: f64) -> String {
+    let (u1, u2) = pair.units();
+    format!(
+        "update({}, v1={v1} {u1}, v2={v2} {u2})",
+        pair.as_coolprop_str()
+    )
+}
+
+/// `value` of the input `param` moved by the relative step `jitter`, or by `jitter` itself when
+/// `value` is zero. Vapor qualities stay within `[0, 1]`.
+fn perturbed_input(param: Param, value: f64, jitter: f64) -> f64 {
+    let perturbed = if value == 0.0 {
+        jitter
+    } else {
+        value * (1.0 + jitter)
+    };
+    if param == Param::Q {
+        perturbed.clamp(0.0, 1.0)
+    } else {
+        perturbed
+    }
+}
+
+/// Whether `err` is one of the solver failures CoolProp reports when a flash does not converge,
+/// as opposed to inputs it rejects outright.
+fn is_convergence_failure(err: &Error) -> bool {
+    let (Error::CoolProp { message, .. } | Error::Computation { message, .. }) = err else {
+        return false;
+    };
+    let message = message.to_ascii_lowercase();
+    [
+        "converge",
+        "maximum number of iterations",
+        "maximum number of steps",
+        "brent's method",
+        "secant",
+        "newton-raphson",
+    ]
+    .iter()
+    .any(|needle| message.contains(needle))
+}
+
+static NAMED_PARAM_IDS: OnceLock<Mutex<HashMap<String, c_long>>> = OnceLock::new();
+
+/// CoolProp parameter index for `name`, cached after the first successful lookup.
+fn named_param_id(name: &str) -> Result<c_long> {
+    let ids = NAMED_PARAM_IDS.get_or_init(Default::default);
+    if let Some(&id) = ids.lock().unwrap_or_else(PoisonError::into_inner).get(name) {
+        return Ok(id);
+    }
+    let c_name = CString::new(name).map_err(|source| Error::EmbeddedNul {
+        label: "output",
+        source,
+    })?;
+    let id = unsafe { crate::ffi::get_param_index(c_name.as_ptr()) };
+    if id < 0 {
+        return Err(Error::InvalidInput(format!(
+            "`{name}` is not a CoolProp parameter name"
+        )));
+    }
+    ids.lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .insert(name.to_owned(), id);
+    Ok(id)
+}
+
+/// Reject an empty composition before it reaches CoolProp as a zero-length array.
+fn require_components(fractions: &[f64]) -> Result<()> {
+    if fractions.is_empty() {
+        return Err(Error::InvalidInput(
+            "composition must have at least one component".into(),
+        ));
+    }
+    Ok(())
+}
+
+/// Shared length of the two input slices of a batched update.
+fn batch_len(value1: &[f64], value2: &[f64]) -> Result<usize> {
+    if value1.len() != value2.len() {
+        return Err(Error::InvalidInput(
+            "value arrays must be the same length".into(),
+        ));
+    }
+    let len = value1.len();
+    if c_long::try_from(len).is_err() {
+        return Err(Error::InvalidInput(format!(
+            "batch of {len} states exceeds the {} states one CoolProp call can take",
+            c_long::MAX
+        )));
+    }
+    Ok(len)
+}
+
 fn buffer_saturated(buf: &[c_char]) -> bool {
     match buf.iter().position(|&c| c == 0) {
         Some(pos) => pos + 1 >= buf.len(),
@@ -1204,6 +2904,20 @@ fn reshape_phase_compositions(flat: &[f64], points: usize, components: usize) ->
     result
 }
 
+fn dew_slope_negative_at(envelope: &PhaseEnvelope, feed: &[f64], p: f64) -> bool {
+    let is_dew = |point: usize| envelope.on_dew_branch(feed, point);
+    let points = envelope.point_count();
+    (1..points).any(|k| {
+        let (p0, p1) = (envelope.pressure[k - 1], envelope.pressure[k]);
+        let brackets = (p0 <= p && p <= p1) || (p1 <= p && p <= p0);
+        if !brackets || p0 == p1 || !is_dew(k - 1) || !is_dew(k) {
+            return false;
+        }
+        let slope = (envelope.temperature[k] - envelope.temperature[k - 1]) / (p1 - p0);
+        slope < 0.0
+    })
+}
+
 fn detect_filled_prefix(a: &[f64], b: &[f64], c: &[f64]) -> usize {
     let len = a.len().min(b.len()).min(c.len());
     let mut last = 0usize;
@@ -1217,7 +2931,170 @@ fn detect_filled_prefix(a: &[f64], b: &[f64], c: &[f64]) -> usize {
 
 #[cfg(test)]
 mod internal_tests {
-    use super::{buffer_saturated, detect_filled_prefix, reshape_phase_compositions};
+    use super::{
+        PhaseEnvelope, batch_len, buffer_saturated, density_is_implausible, describe_update,
+        detect_filled_prefix, dew_slope_negative_at, is_convergence_failure, is_missing_constant,
+        molar_density_reads_as_mass, normalize_fractions, order_named_fractions, perturbed_input,
+        require_components, reshape_phase_compositions,
+    };
+    use crate::{Error, InputPair, Param};
+    use std::collections::HashMap;
+
+    #[test]
+    fn convergence_failures_are_classified() {
+        let coolprop = |message: &str| Error::CoolProp {
+            code: 1,
+            message: message.into(),
+        };
+        assert!(is_convergence_failure(&coolprop(
+            "Brent's method f(b) is NAN for b = 300"
+        )));
+        assert!(is_convergence_failure(&coolprop(
+            "reached maximum number of iterations"
+        )));
+        assert!(!is_convergence_failure(&coolprop(
+            "Input pair variable is invalid"
+        )));
+        assert!(is_convergence_failure(&Error::Computation {
+            context: "update(PT_INPUTS, v1=101325 Pa, v2=300 K)".into(),
+            message: "Secant failed to converge".into(),
+        }));
+        assert!(!is_convergence_failure(&Error::InvalidInput(
+            "did not converge".into()
+        )));
+        assert!(!is_convergence_failure(&coolprop(
+            "iteration count must be positive"
+        )));
+    }
+
+    #[test]
+    fn missing_constants_are_classified() {
+        let coolprop = |message: &str| Error::CoolProp {
+            code: 1,
+            message: message.into(),
+        };
+        assert!(is_missing_constant(&coolprop(
+            "calc_T_critical is not implemented for this backend"
+        )));
+        assert!(!is_missing_constant(&coolprop(
+            "Brent's method f(b) is NAN for b = 300"
+        )));
+        assert!(!is_missing_constant(&Error::InvalidInput(
+            "state not initialized; call update first".into()
+        )));
+    }
+
+    #[test]
+    fn perturbed_inputs_move_zeros_and_keep_qualities_valid() {
+        assert_eq!(perturbed_input(Param::P, 1.0e5, 1e-3), 1.0e5 * (1.0 + 1e-3));
+        assert_eq!(perturbed_input(Param::Hmass, 0.0, -1e-3), -1e-3);
+        assert_eq!(perturbed_input(Param::Q, 1.0, 1e-3), 1.0);
+        assert_eq!(perturbed_input(Param::Q, 1.0, -1e-3), 1.0 - 1e-3);
+        assert_eq!(perturbed_input(Param::Q, 0.0, -1e-3), 0.0);
+        assert_eq!(perturbed_input(Param::Q, 0.0, 1e-3), 1e-3);
+    }
+
+    #[test]
+    fn update_description_annotates_units() {
+        assert_eq!(
+            describe_update(InputPair::PT, 101325.0, 300.0),
+            "update(PT_INPUTS, v1=101325 Pa, v2=300 K)"
+        );
+        assert_eq!(
+            describe_update(InputPair::DmassQ, 2.5, 0.0),
+            "update(DmassQ_INPUTS, v1=2.5 kg/m^3, v2=0 mol/mol)"
+        );
+    }
+
+    #[test]
+    fn named_fractions_follow_component_order() {
+        let components = vec![String::from("R32"), String::from("R125")];
+        let named = HashMap::from([("R125", 0.3), ("R32", 0.7)]);
+        assert_eq!(
+            order_named_fractions(&components, &named).unwrap(),
+            vec![0.7, 0.3]
+        );
+
+        let missing = HashMap::from([("R32", 1.0)]);
+        assert!(order_named_fractions(&components, &missing).is_err());
+        let unknown = HashMap::from([("R32", 0.5), ("R125", 0.3), ("R1234yf", 0.2)]);
+        assert!(order_named_fractions(&components, &unknown).is_err());
+    }
+
+    #[test]
+    fn fractions_are_normalized() {
+        assert_eq!(normalize_fractions(&[0.3, 0.3]).unwrap(), vec![0.5, 0.5]);
+        assert_eq!(
+            normalize_fractions(&[2.0, 0.0, 2.0]).unwrap(),
+            vec![0.5, 0.0, 0.5]
+        );
+        assert!(normalize_fractions(&[0.0, 0.0]).is_err());
+        assert!(normalize_fractions(&[]).is_err());
+        assert!(normalize_fractions(&[0.5, -0.1]).is_err());
+        assert!(normalize_fractions(&[0.5, f64::NAN]).is_err());
+    }
+
+    #[test]
+    fn molar_density_passed_as_mass_density_is_implausible() {
+        // Water: reducing density 322 kg/m^3, liquid molar density about 55 500 mol/m^3.
+        assert!(!density_is_implausible(998.0, 322.0));
+        assert!(!density_is_implausible(0.6, 322.0));
+        assert!(density_is_implausible(55_500.0, 322.0));
+        assert!(!density_is_implausible(55_500.0, f64::NAN));
+    }
+
+    #[test]
+    fn mass_density_passed_as_molar_density_is_flagged() {
+        // Water: reducing density 17 874 mol/m^3, molar mass 0.018 kg/mol.
+        let (rho_reducing, molar_mass) = (17_873.7, 0.018_015);
+        // Liquid water in kg/m^3 mistaken for mol/m^3.
+        assert!(molar_density_reads_as_mass(998.0, rho_reducing, molar_mass));
+        // The correct liquid molar density, and a dilute vapor.
+        assert!(!molar_density_reads_as_mass(
+            55_400.0,
+            rho_reducing,
+            molar_mass
+        ));
+        assert!(!molar_density_reads_as_mass(40.0, rho_reducing, molar_mass));
+        assert!(!molar_density_reads_as_mass(998.0, rho_reducing, f64::NAN));
+    }
+
+    #[cfg(feature = "log")]
+    #[test]
+    fn ffi_call_context_names_the_wrapper() {
+        use super::ffi_call_context;
+
+        assert_eq!(
+            ffi_call_context("coolprop::abstract_state::AbstractState::update::{{closure}}"),
+            "AbstractState::update"
+        );
+        assert_eq!(
+            ffi_call_context(
+                "coolprop::abstract_state::AbstractState::mole_fractions::{{closure}}::{{closure}}"
+            ),
+            "AbstractState::mole_fractions"
+        );
+        assert_eq!(ffi_call_context("other::path"), "other::path");
+    }
+
+    #[test]
+    fn empty_compositions_are_rejected() {
+        assert!(matches!(
+            require_components(&[]),
+            Err(Error::InvalidInput(msg)) if msg.contains("at least one component")
+        ));
+        assert!(require_components(&[1.0]).is_ok());
+    }
+
+    #[test]
+    fn batch_len_requires_matching_slices() {
+        assert_eq!(batch_len(&[], &[]).unwrap(), 0);
+        assert_eq!(batch_len(&[1.0, 2.0], &[3.0, 4.0]).unwrap(), 2);
+        assert!(matches!(
+            batch_len(&[1.0], &[]),
+            Err(Error::InvalidInput(_))
+        ));
+    }
 
     #[test]
     fn buffer_saturated_detection() {
@@ -1260,4 +3137,73 @@ mod internal_tests {
         let c2 = [f64::NAN, f64::NAN];
         assert_eq!(detect_filled_prefix(&a2, &b2, &c2), 0);
     }
+
+    #[test]
+    fn envelope_branches_are_interpolated_separately() {
+        // Dew side up to the critical point at index 3, where the feed-carrying vapor becomes
+        // the denser phase, then down the bubble side.
+        let envelope = PhaseEnvelope {
+            temperature: vec![250.0, 280.0, 300.0, 310.0, 300.0, 270.0],
+            pressure: vec![1.0e5, 1.0e6, 3.0e6, 4.0e6, 3.0e6, 1.0e6],
+            rhomolar_liq: vec![12e3, 11e3, 9e3, 6e3, 4e3, 1e3],
+            rhomolar_vap: vec![50.0, 500.0, 2e3, 6e3, 8e3, 11e3],
+            x: vec![vec![0.9; 6], vec![0.1; 6]],
+            y: vec![vec![0.5; 6], vec![0.5; 6]],
+        };
+        let (t_bubble, t_dew) = envelope.at_pressure(1.0e6).unwrap();
+        assert_eq!((t_bubble, t_dew), (270.0, 280.0));
+        let (t_bubble, t_dew) = envelope.at_pressure(2.0e6).unwrap();
+        let w = (2.0e6f64.ln() - 1.0e6f64.ln()) / (3.0e6f64.ln() - 1.0e6f64.ln());
+        assert!((t_dew - (280.0 + 20.0 * w)).abs() < 1e-12);
+        assert!((t_bubble - (270.0 + 30.0 * w)).abs() < 1e-12);
+        // Only the dew branch reaches down to 0.5 MPa.
+        assert_eq!(envelope.at_pressure(5.0e5), None);
+        assert_eq!(envelope.at_pressure(5.0e6), None);
+        assert_eq!(envelope.at_pressure(f64::NAN), None);
+    }
+
+    #[test]
+    fn dew_slope_detects_retrograde_segment() {
+        // One traced path with the feed in `y` throughout. Dew side: T rises to a cricondentherm
+        // at 3 MPa, then falls to the critical point between indices 3 and 4, where the
+        // feed-carrying vapor becomes the denser phase. The bubble side climbs to a cricondenbar
+        // at 4.8 MPa and falls again.
+        let envelope = PhaseEnvelope {
+            temperature: vec![250.0, 280.0, 290.0, 285.0, 280.0, 270.0, 250.0],
+            pressure: vec![1.0e6, 2.0e6, 3.0e6, 4.0e6, 4.5e6, 4.8e6, 4.0e6],
+            rhomolar_liq: vec![12e3, 11e3, 9e3, 7e3, 5e3, 4e3, 2e3],
+            rhomolar_vap: vec![500.0, 1e3, 2e3, 4e3, 6e3, 8e3, 10e3],
+            x: vec![vec![0.9; 7], vec![0.1; 7]],
+            y: vec![vec![0.5; 7], vec![0.5; 7]],
+        };
+        let feed = [0.5, 0.5];
+        assert!(!dew_slope_negative_at(&envelope, &feed, 1.5e6));
+        assert!(dew_slope_negative_at(&envelope, &feed, 3.5e6));
+        // T also falls with rising P on the bubble side below the cricondenbar.
+        assert!(!dew_slope_negative_at(&envelope, &feed, 4.6e6));
+        // Bracketed by a segment crossing the critical point and a bubble-side segment.
+        assert!(!dew_slope_negative_at(&envelope, &feed, 4.2e6));
+        // Outside the envelope.
+        assert!(!dew_slope_negative_at(&envelope, &feed, 9.0e6));
+    }
+
+    #[test]
+    fn truncated_composition_rows_limit_the_envelope() {
+        // A hand-built envelope whose second `y` row stops after two points: only the first
+        // segment is usable, and nothing past it may be indexed.
+        let mut envelope = PhaseEnvelope {
+            temperature: vec![250.0, 280.0, 300.0, 310.0, 300.0, 270.0],
+            pressure: vec![1.0e5, 1.0e6, 3.0e6, 4.0e6, 3.0e6, 1.0e6],
+            rhomolar_liq: vec![12e3, 11e3, 9e3, 6e3, 4e3, 1e3],
+            rhomolar_vap: vec![50.0, 500.0, 2e3, 6e3, 8e3, 11e3],
+            x: vec![vec![0.9; 6], vec![0.1; 6]],
+            y: vec![vec![0.5; 6], vec![0.5; 2]],
+        };
+        let feed = [0.5, 0.5];
+        assert_eq!(envelope.at_pressure(2.0e6), None);
+        assert!(!dew_slope_negative_at(&envelope, &feed, 3.5e6));
+        envelope.y[1].clear();
+        assert_eq!(envelope.at_pressure(5.0e5), None);
+        assert!(!dew_slope_negative_at(&envelope, &feed, 5.0e5));
+    }
 }
@@ -1,4 +1,4 @@
-use std::ffi::NulError;
+use std::{ffi::NulError, str::Utf8Error};
 
 use thiserror::Error;
 
@@ -60,4 +60,8 @@ pub enum Error {
         /// Original UTF-8 to C-string conversion error.
         source: NulError,
     },
+
+    /// CoolProp returned a string that is not valid UTF-8.
+    #[error("CoolProp returned a string that is not valid UTF-8")]
+    InvalidUtf8(#[source] Utf8Error),
 }
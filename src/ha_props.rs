@@ -1,13 +1,17 @@
 //! Psychrometric (humid air) property calculations.
 //!
 //! This module provides the [`ha_props_si`] function for calculating properties of moist air
-//! mixtures. It implements psychrometric calculations following ASHRAE standards and is
-//! essential for HVAC design, drying processes, and climate control applications.
+//! mixtures, plus [`ha_props_si_atm`] for the common case of standard atmospheric pressure. It
+//! implements psychrometric calculations following ASHRAE standards and is essential for HVAC
+//! design, drying processes, and climate control applications.
 
 use std::ffi::CString;
 
 use crate::{Error, Result, check_finite_and_report_error, ffi};
 
+/// Standard atmospheric pressure at sea level, in pascals.
+pub const STANDARD_ATMOSPHERE: f64 = 101_325.0;
+
 /// Calculate psychrometric (humid air) properties.
 ///
 /// This function computes properties of moist air (mixtures of dry air and water vapor) given
@@ -330,3 +334,361 @@ pub fn ha_props_si(
     };
     check_finite_and_report_error(value, &context)
 }
+
+/// Calculate psychrometric properties at standard atmospheric pressure.
+///
+/// Equivalent to [`ha_props_si`] with the pressure input fixed to `"P" = 101 325 Pa`
+/// ([`STANDARD_ATMOSPHERE`]), so only the temperature and composition inputs remain. The two
+/// inputs must not include `P` themselves.
+///
+/// Sea-level pressure is an assumption, not a default that can be ignored: humidity ratio,
+/// wet-bulb temperature, and specific volume all depend on pressure. Altitude-sensitive
+/// calculations, or any site well away from sea level, must use [`ha_props_si`] with the actual
+/// pressure.
+///
+/// # Examples
+///
+/// ```rust
+/// use coolprop::{ha_props_si, ha_props_si_atm};
+///
+/// # fn main() -> coolprop::Result<()> {
+/// # if cfg!(cp_docs_rs) { return Ok(()); }
+/// let w = ha_props_si_atm("W", "T", 300.0, "R", 0.5)?;
+/// assert_eq!(w, ha_props_si("W", "T", 300.0, "P", 101_325.0, "R", 0.5)?);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Same as [`ha_props_si`].
+pub fn ha_props_si_atm(
+    output: &str,
+    name2: &str,
+    prop2: f64,
+    name3: &str,
+    prop3: f64,
+) -> Result<f64> {
+    ha_props_si(output, "P", STANDARD_ATMOSPHERE, name2, prop2, name3, prop3)
+}
+
+/// Density of moist air in kilograms of humid air per cubic meter, at dry-bulb temperature `t`
+/// (K), pressure `p` (Pa), and relative humidity `rh` (0-1).
+///
+/// The reciprocal of `Vha`, the volume per kilogram of humid air (dry air plus water vapor).
+/// This is the density to use with a total mass flow rate; see
+/// [`moist_air_density_dry_basis`] when flows are expressed per kilogram of dry air.
+///
+/// # Errors
+///
+/// Same as [`ha_props_si`].
+pub fn moist_air_density(t: f64, p: f64, rh: f64) -> Result<f64> {
+    Ok(1.0 / ha_props_si("Vha", "T", t, "P", p, "R", rh)?)
+}
+
+/// Dry air mass per unit volume of moist air, in kg_da/m³, at dry-bulb temperature `t` (K),
+/// pressure `p` (Pa), and relative humidity `rh` (0-1).
+///
+/// The reciprocal of `Vda`, the volume per kilogram of dry air. It is smaller than
+/// [`moist_air_density`] by the factor `1 + W`, where `W` is the humidity ratio, and converts a
+/// volumetric flow into the dry-air mass flow that psychrometric enthalpies are based on.
+///
+/// # Errors
+///
+/// Same as [`ha_props_si`].
+pub fn moist_air_density_dry_basis(t: f64, p: f64, rh: f64) -> Result<f64> {
+    Ok(1.0 / ha_props_si("Vda", "T", t, "P", p, "R", rh)?)
+}
+
+/// Isobaric specific heat of moist air per kilogram of dry air, in J/(kg_da·K), at dry-bulb
+/// temperature `t` (K), pressure `p` (Pa), and relative humidity `rh` (0-1).
+///
+/// This is CoolProp's `cp` humid-air output, the derivative of `Hda` with respect to the
+/// dry-bulb temperature at constant pressure and humidity ratio, so multiplying it by a dry-air
+/// mass flow gives the sensible heat rate per kelvin. Divide by `1 + W` for the value per
+/// kilogram of humid air (CoolProp's `cp_ha`).
+///
+/// # Errors
+///
+/// Same as [`ha_props_si`].
+pub fn moist_air_specific_heat(t: f64, p: f64, rh: f64) -> Result<f64> {
+    ha_props_si("cp", "T", t, "P", p, "R", rh)
+}
+
+/// Sensible and latent parts of the enthalpy change between two moist-air states, in J/kg_da,
+/// as `(sensible, latent)`.
+///
+/// Each state is `(t_dry, p, rh)` in K, Pa, and 0-1. The process is split at the intermediate
+/// state with the inlet dry-bulb temperature and the outlet humidity ratio `W_out`:
+///
+/// - latent = `h(T_in, W_out) - h_in`, the humidity change at constant temperature;
+/// - sensible = `h_out - h(T_in, W_out)`, the temperature change at constant humidity ratio.
+///
+/// The two sum to `h_out - h_in`. Both are negative for a cooling and dehumidifying coil, and
+/// their ratio gives the sensible heat ratio. Splitting at the drier intermediate keeps it
+/// below saturation for dehumidifying processes; the intermediate uses the outlet pressure.
+///
+/// # Errors
+///
+/// Propagates errors from [`ha_props_si`] for any of the states.
+pub fn sensible_latent_split(
+    inlet: (f64, f64, f64),
+    outlet: (f64, f64, f64),
+) -> Result<(f64, f64)> {
+    let (t_in, p_in, rh_in) = inlet;
+    let (t_out, p_out, rh_out) = outlet;
+    let h_in = ha_props_si("Hda", "T", t_in, "P", p_in, "R", rh_in)?;
+    let h_out = ha_props_si("Hda", "T", t_out, "P", p_out, "R", rh_out)?;
+    let w_out = ha_props_si("W", "T", t_out, "P", p_out, "R", rh_out)?;
+    let h_mid = ha_props_si("Hda", "T", t_in, "P", p_out, "W", w_out)?;
+    Ok((h_out - h_mid, h_mid - h_in))
+}
+
+/// Adiabatic mixing of two moist-air streams, returning the mixed dry-bulb temperature (K) and
+/// humidity ratio (kg_w/kg_da) as `(t_mix, w_mix)`.
+///
+/// Each stream is `(mdot_da, t_dry, p, rh)`: dry-air mass flow (kg_da/s, or any consistent
+/// unit), dry-bulb temperature (K), pressure (Pa), and relative humidity (0-1). Water and
+/// energy balances per unit dry air give
+///
+/// `w_mix = (m1 w1 + m2 w2) / (m1 + m2)` and `h_mix = (m1 h1 + m2 h2) / (m1 + m2)`,
+///
+/// with `h` the [`ha_props_si`] enthalpy `Hda`. The temperature is then solved from
+/// `(h_mix, w_mix)` at the flow-weighted mean pressure. Weighting by dry-air rather than total
+/// mass flow is what makes both balances exact. If the mixed state would be supersaturated
+/// (fog), CoolProp's correlations reject it and the error is returned.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if a flow is negative or not finite, or both are zero, and
+/// propagates errors from [`ha_props_si`].
+pub fn mix_airstreams(
+    stream1: (f64, f64, f64, f64),
+    stream2: (f64, f64, f64, f64),
+) -> Result<(f64, f64)> {
+    let (m1, t1, p1, rh1) = stream1;
+    let (m2, t2, p2, rh2) = stream2;
+    if !(m1.is_finite() && m2.is_finite() && m1 >= 0.0 && m2 >= 0.0 && m1 + m2 > 0.0) {
+        return Err(Error::InvalidInput(format!(
+            "dry-air mass flows must be finite, non-negative, and not both zero, got {m1} and {m2}"
+        )));
+    }
+    let m = m1 + m2;
+    let w1 = ha_props_si("W", "T", t1, "P", p1, "R", rh1)?;
+    let w2 = ha_props_si("W", "T", t2, "P", p2, "R", rh2)?;
+    let h1 = ha_props_si("Hda", "T", t1, "P", p1, "R", rh1)?;
+    let h2 = ha_props_si("Hda", "T", t2, "P", p2, "R", rh2)?;
+    let w_mix = (m1 * w1 + m2 * w2) / m;
+    let h_mix = (m1 * h1 + m2 * h2) / m;
+    let p_mix = (m1 * p1 + m2 * p2) / m;
+    let t_mix = ha_props_si("T", "Hda", h_mix, "P", p_mix, "W", w_mix)?;
+    Ok((t_mix, w_mix))
+}
+
+/// A complete psychrometric state point, computed once from dry-bulb temperature, pressure, and
+/// relative humidity.
+///
+/// All fields are SI and follow the conventions of [`ha_props_si`]: enthalpy and specific
+/// volume are per kilogram of **dry air**. With the `serde` feature the struct can be
+/// serialized, for example to log operating points.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HumidAirState {
+    /// Dry-bulb temperature, in kelvin.
+    pub t_dry: f64,
+    /// Wet-bulb temperature, in kelvin.
+    pub t_wet: f64,
+    /// Dew-point temperature, in kelvin.
+    pub t_dew: f64,
+    /// Pressure, in pascals.
+    pub pressure: f64,
+    /// Humidity ratio, in kg_w/kg_da.
+    pub humidity_ratio: f64,
+    /// Relative humidity, as a fraction (0-1).
+    pub relative_humidity: f64,
+    /// Mixture enthalpy per unit dry air, in J/kg_da.
+    pub enthalpy: f64,
+    /// Mixture volume per unit dry air, in m³/kg_da.
+    pub specific_volume: f64,
+}
+
+impl HumidAirState {
+    /// Evaluate every field with [`ha_props_si`] at dry-bulb temperature `t_dry` (K), pressure
+    /// `p` (Pa), and relative humidity `rh` (0-1).
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error raised by [`ha_props_si`] for any of the outputs.
+    pub fn from(t_dry: f64, p: f64, rh: f64) -> Result<Self> {
+        let at = |output: &str| ha_props_si(output, "T", t_dry, "P", p, "R", rh);
+        Ok(Self {
+            t_dry,
+            t_wet: at("Twb")?,
+            t_dew: at("Tdp")?,
+            pressure: p,
+            humidity_ratio: at("W")?,
+            relative_humidity: rh,
+            enthalpy: at("Hda")?,
+            specific_volume: at("Vda")?,
+        })
+    }
+}
+
+// Triple point of water (IAPWS), where the dew-point and frost-point curves meet.
+const WATER_TRIPLE_T: f64 = 273.16;
+const WATER_TRIPLE_P: f64 = 611.657;
+// Lower temperature limit of CoolProp's humid-air correlations, in kelvin.
+const HUMID_AIR_T_MIN: f64 = 173.15;
+
+/// Dew point above freezing and frost point below it, for humid air at dry-bulb temperature
+/// `t_dry` (K), pressure `p` (Pa), and relative humidity `rh` (0-1).
+///
+/// The water-vapor partial pressure `psi_w` is taken from [`ha_props_si`] and the temperature
+/// at which it equals a saturation pressure is returned:
+///
+/// - `psi_w` at or above the triple-point pressure of water (611.657 Pa): the **dew point**,
+///   where vapor condenses to liquid, from the liquid-vapor saturation line.
+/// - Below it: the **frost point**, where vapor deposits as ice, from the IAPWS (2011)
+///   sublimation-pressure equation. Below freezing the frost point lies above the dew point
+///   over supercooled water, so it is the temperature at which a surface first collects
+///   moisture.
+///
+/// Both curves meet at the triple point (273.16 K), so the result is continuous across the
+/// transition. Neither branch applies the small enhancement factor CoolProp uses for
+/// `ha_props_si("Tdp", ...)`, so above freezing the two can differ by a few hundredths of a
+/// kelvin.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if `rh` is not in `(0, 1]`, and [`Error::Computation`] if
+/// the humid-air correlations reject the inputs or the frost point falls below their range
+/// (173.15 K).
+pub fn dew_or_frost_point(t_dry: f64, p: f64, rh: f64) -> Result<f64> {
+    if !(rh > 0.0 && rh <= 1.0) {
+        return Err(Error::InvalidInput(format!(
+            "relative humidity must be in (0, 1], got {rh}"
+        )));
+    }
+    let context = format!("dew_or_frost_point(T={t_dry} K, P={p} Pa, RH={rh})");
+    let psi_w =
+        ha_props_si("psi_w", "T", t_dry, "P", p, "R", rh).map_err(|err| Error::Computation {
+            context: context.clone(),
+            message: format!(
+                "humid-air correlations (valid from {HUMID_AIR_T_MIN} K) rejected the state: {err}"
+            ),
+        })?;
+    if psi_w >= WATER_TRIPLE_P {
+        return crate::props_si("T", "P", psi_w, "Q", 1.0, "Water");
+    }
+    if psi_w < sublimation_pressure(HUMID_AIR_T_MIN) {
+        return Err(Error::Computation {
+            context,
+            message: format!(
+                "frost point is below {HUMID_AIR_T_MIN} K, outside the humid-air correlation range"
+            ),
+        });
+    }
+    // The sublimation pressure is strictly increasing, so bisection on ln(p) is robust.
+    let target = psi_w.ln();
+    let (mut lo, mut hi) = (HUMID_AIR_T_MIN, WATER_TRIPLE_T);
+    while hi - lo > 1e-9 {
+        let mid = 0.5 * (lo + hi);
+        if sublimation_pressure(mid).ln() < target {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    Ok(0.5 * (lo + hi))
+}
+
+/// Adiabatic saturation temperature (K) of humid air at dry-bulb temperature `t_dry` (K),
+/// pressure `p` (Pa), and humidity ratio `w` (kg_w/kg_da).
+///
+/// This is the temperature `T_as` at which air leaving a long, insulated channel over liquid
+/// water is saturated, with the evaporated water supplied at `T_as`. Per kilogram of dry air the
+/// energy balance is
+///
+/// `h(t_dry, w) + (W_s(T_as) - w) h_liq(T_as) = h_s(T_as)`,
+///
+/// where `h` and `h_s` are the humid-air enthalpies (`Hda`) of the inlet and of saturated air,
+/// `W_s` is the saturation humidity ratio, all from [`ha_props_si`], and `h_liq` is the enthalpy
+/// of saturated liquid water from [`props_si`](crate::props_si); the pressure dependence of the
+/// liquid enthalpy is negligible here. The root is found by bisection between the triple point
+/// of water and `t_dry`.
+///
+/// `T_as` is the *thermodynamic* wet-bulb temperature, a property of the air alone. A real
+/// wet-bulb thermometer reads a *psychrometric* wet-bulb temperature set by simultaneous heat
+/// and mass transfer at its wick; for air-water mixtures the two nearly coincide because the
+/// Lewis number is close to one, but they are not the same quantity.
+///
+/// `h_liq` uses CoolProp's default reference state for water, which is also the one the
+/// humid-air correlations use; changing it with
+/// [`set_reference_state`](crate::set_reference_state) skews the result.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if `w` is negative or exceeds the saturation humidity ratio
+/// at `t_dry`, and [`Error::Computation`] if `T_as` would lie below the triple point of water,
+/// where the evaporating water would be ice. Errors from the property calls are propagated.
+pub fn adiabatic_saturation_temperature(t_dry: f64, p: f64, w: f64) -> Result<f64> {
+    if !(w.is_finite() && w >= 0.0) {
+        return Err(Error::InvalidInput(format!(
+            "humidity ratio must be finite and non-negative, got {w}"
+        )));
+    }
+    let w_sat = |t: f64| ha_props_si("W", "T", t, "P", p, "R", 1.0);
+    if w > w_sat(t_dry)? {
+        return Err(Error::InvalidInput(format!(
+            "humidity ratio {w} exceeds saturation at T = {t_dry} K, P = {p} Pa"
+        )));
+    }
+    let h_in = ha_props_si("Hda", "T", t_dry, "P", p, "W", w)?;
+    // Positive above T_as: saturated air there holds more energy than the inlet plus make-up
+    // water.
+    let residual = |t: f64| -> Result<f64> {
+        let h_sat = ha_props_si("Hda", "T", t, "P", p, "R", 1.0)?;
+        let h_liq = crate::props_si("Hmass", "T", t, "Q", 0.0, "Water")?;
+        Ok(h_sat - h_in - (w_sat(t)? - w) * h_liq)
+    };
+    if residual(t_dry)? <= 0.0 {
+        return Ok(t_dry);
+    }
+    if residual(WATER_TRIPLE_T)? > 0.0 {
+        return Err(Error::Computation {
+            context: format!("adiabatic_saturation_temperature(T={t_dry} K, P={p} Pa, W={w})"),
+            message: format!("adiabatic saturation temperature is below {WATER_TRIPLE_T} K"),
+        });
+    }
+    let (mut lo, mut hi) = (WATER_TRIPLE_T, t_dry);
+    while hi - lo > 1e-6 {
+        let mid = 0.5 * (lo + hi);
+        if residual(mid)? > 0.0 {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+    Ok(0.5 * (lo + hi))
+}
+
+/// Sublimation pressure of ice Ih in pascals (IAPWS R14-08(2011)), valid from 50 K to 273.16 K.
+fn sublimation_pressure(t: f64) -> f64 {
+    const A: [f64; 3] = [-0.212_144_006e2, 0.273_203_819e2, -0.610_598_130e1];
+    const B: [f64; 3] = [0.333_333_333e-2, 0.120_666_667e1, 0.170_333_333e1];
+    let theta = t / WATER_TRIPLE_T;
+    let sum: f64 = A.iter().zip(B).map(|(a, b)| a * theta.powf(b)).sum();
+    WATER_TRIPLE_P * (sum / theta).exp()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WATER_TRIPLE_P, WATER_TRIPLE_T, sublimation_pressure};
+
+    #[test]
+    fn sublimation_pressure_matches_iapws_check_values() {
+        assert!((sublimation_pressure(WATER_TRIPLE_T) - WATER_TRIPLE_P).abs() < 1e-9);
+        // Check value from IAPWS R14-08(2011).
+        assert!((sublimation_pressure(230.0) / 8.947_352_740_189 - 1.0).abs() < 1e-9);
+    }
+}
@@ -4,7 +4,7 @@ use std::{
     sync::OnceLock,
 };
 
-use crate::Result;
+use crate::{Error, Result};
 
 /// Thermodynamic phase labels exposed by the CoolProp C API.
 #[non_exhaustive]
@@ -23,7 +23,21 @@ pub enum Phase {
 }
 
 impl Phase {
-    pub(crate) fn from_code(code: c_int) -> Option<Self> {
+    /// Every phase label in declaration order, which is also CoolProp's code order.
+    pub const ALL: &'static [Phase] = &[
+        Phase::Liquid,
+        Phase::Supercritical,
+        Phase::SupercriticalGas,
+        Phase::SupercriticalLiquid,
+        Phase::CriticalPoint,
+        Phase::Gas,
+        Phase::TwoPhase,
+        Phase::Unknown,
+        Phase::NotImposed,
+    ];
+
+    /// Phase for a CoolProp integer phase code, or `None` if the code is not recognised.
+    pub fn from_code(code: c_int) -> Option<Self> {
         match code {
             0 => Some(Self::Liquid),
             1 => Some(Self::Supercritical),
@@ -38,6 +52,21 @@ impl Phase {
         }
     }
 
+    /// CoolProp integer phase code; the inverse of [`from_code`](Self::from_code).
+    pub fn as_code(self) -> c_int {
+        match self {
+            Self::Liquid => 0,
+            Self::Supercritical => 1,
+            Self::SupercriticalGas => 2,
+            Self::SupercriticalLiquid => 3,
+            Self::CriticalPoint => 4,
+            Self::Gas => 5,
+            Self::TwoPhase => 6,
+            Self::Unknown => 7,
+            Self::NotImposed => 8,
+        }
+    }
+
     pub(crate) fn specifier_token(self) -> &'static str {
         match self {
             Self::Liquid => "phase_liquid",
@@ -79,6 +108,35 @@ impl std::fmt::Display for Phase {
     }
 }
 
+/// Parses CoolProp tokens (`"phase_twophase"`), the labels returned by
+/// [`phase_si`](crate::phase_si) (`"twophase"`) and the [`Display`](std::fmt::Display) labels
+/// (`"two-phase"`). Case and `-`, `_` or space separators are ignored.
+impl std::str::FromStr for Phase {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        // Compare with separators and case removed, and without the `phase` prefix of the
+        // CoolProp tokens, so every accepted spelling reduces to the same key.
+        let key = |token: &str| -> String {
+            let squashed: String = token
+                .chars()
+                .filter(|c| !matches!(c, '-' | '_' | ' '))
+                .map(|c| c.to_ascii_lowercase())
+                .collect();
+            squashed
+                .strip_prefix("phase")
+                .map(str::to_owned)
+                .unwrap_or(squashed)
+        };
+        let wanted = key(s);
+        Phase::ALL
+            .iter()
+            .copied()
+            .find(|phase| key(phase.specifier_token()) == wanted)
+            .ok_or_else(|| Error::InvalidInput(format!("unknown phase `{s}`")))
+    }
+}
+
 macro_rules! coolprop_input_pairs {
     ($( $variant:ident => $name:literal ),+ $(,)?) => {
         #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -154,6 +212,148 @@ coolprop_input_pairs! {
     TUmass => "TUmass_INPUTS",
 }
 
+impl InputPair {
+    /// The two properties this pair supplies, in the order their values are passed to
+    /// [`AbstractState::update`](crate::AbstractState::update).
+    ///
+    /// For example, `InputPair::PT.params()` is `(Param::P, Param::T)` and
+    /// `InputPair::QT.params()` is `(Param::Q, Param::T)`.
+    pub fn params(self) -> (Param, Param) {
+        match self {
+            InputPair::PT => (Param::P, Param::T),
+            InputPair::QT => (Param::Q, Param::T),
+            InputPair::PQ => (Param::P, Param::Q),
+            InputPair::QSmolar => (Param::Q, Param::Smolar),
+            InputPair::QSmass => (Param::Q, Param::Smass),
+            InputPair::HmolarQ => (Param::Hmolar, Param::Q),
+            InputPair::HmassQ => (Param::Hmass, Param::Q),
+            InputPair::DmolarQ => (Param::Dmolar, Param::Q),
+            InputPair::DmassQ => (Param::Dmass, Param::Q),
+            InputPair::HmolarP => (Param::Hmolar, Param::P),
+            InputPair::HmassP => (Param::Hmass, Param::P),
+            InputPair::PSmolar => (Param::P, Param::Smolar),
+            InputPair::PSmass => (Param::P, Param::Smass),
+            InputPair::PUmolar => (Param::P, Param::Umolar),
+            InputPair::PUmass => (Param::P, Param::Umass),
+            InputPair::HmolarSmolar => (Param::Hmolar, Param::Smolar),
+            InputPair::HmassSmass => (Param::Hmass, Param::Smass),
+            InputPair::SmolarT => (Param::Smolar, Param::T),
+            InputPair::SmassT => (Param::Smass, Param::T),
+            InputPair::DmolarT => (Param::Dmolar, Param::T),
+            InputPair::DmassT => (Param::Dmass, Param::T),
+            InputPair::DmolarP => (Param::Dmolar, Param::P),
+            InputPair::DmassP => (Param::Dmass, Param::P),
+            InputPair::DmolarHmolar => (Param::Dmolar, Param::Hmolar),
+            InputPair::DmassHmass => (Param::Dmass, Param::Hmass),
+            InputPair::DmolarSmolar => (Param::Dmolar, Param::Smolar),
+            InputPair::DmassSmass => (Param::Dmass, Param::Smass),
+            InputPair::DmolarUmolar => (Param::Dmolar, Param::Umolar),
+            InputPair::DmassUmass => (Param::Dmass, Param::Umass),
+            InputPair::HmolarT => (Param::Hmolar, Param::T),
+            InputPair::HmassT => (Param::Hmass, Param::T),
+            InputPair::TUmolar => (Param::T, Param::Umolar),
+            InputPair::TUmass => (Param::T, Param::Umass),
+        }
+    }
+
+    /// Human-readable description of the pair, such as `"Pressure and temperature"`.
+    ///
+    /// The properties are listed in the order their values are passed to
+    /// [`AbstractState::update`](crate::AbstractState::update), which makes this suitable for UIs
+    /// and error messages where the raw `PT_INPUTS` token is opaque. CoolProp's C API has no
+    /// lookup for input-pair descriptions, so the text is maintained here.
+    pub fn description(self) -> &'static str {
+        match self {
+            InputPair::PT => "Pressure and temperature",
+            InputPair::QT => "Vapor quality and temperature",
+            InputPair::PQ => "Pressure and vapor quality",
+            InputPair::QSmolar => "Vapor quality and molar entropy",
+            InputPair::QSmass => "Vapor quality and mass entropy",
+            InputPair::HmolarQ => "Molar enthalpy and vapor quality",
+            InputPair::HmassQ => "Mass enthalpy and vapor quality",
+            InputPair::DmolarQ => "Molar density and vapor quality",
+            InputPair::DmassQ => "Mass density and vapor quality",
+            InputPair::HmolarP => "Molar enthalpy and pressure",
+            InputPair::HmassP => "Mass enthalpy and pressure",
+            InputPair::PSmolar => "Pressure and molar entropy",
+            InputPair::PSmass => "Pressure and mass entropy",
+            InputPair::PUmolar => "Pressure and molar internal energy",
+            InputPair::PUmass => "Pressure and mass internal energy",
+            InputPair::HmolarSmolar => "Molar enthalpy and molar entropy",
+            InputPair::HmassSmass => "Mass enthalpy and mass entropy",
+            InputPair::SmolarT => "Molar entropy and temperature",
+            InputPair::SmassT => "Mass entropy and temperature",
+            InputPair::DmolarT => "Molar density and temperature",
+            InputPair::DmassT => "Mass density and temperature",
+            InputPair::DmolarP => "Molar density and pressure",
+            InputPair::DmassP => "Mass density and pressure",
+            InputPair::DmolarHmolar => "Molar density and molar enthalpy",
+            InputPair::DmassHmass => "Mass density and mass enthalpy",
+            InputPair::DmolarSmolar => "Molar density and molar entropy",
+            InputPair::DmassSmass => "Mass density and mass entropy",
+            InputPair::DmolarUmolar => "Molar density and molar internal energy",
+            InputPair::DmassUmass => "Mass density and mass internal energy",
+            InputPair::HmolarT => "Molar enthalpy and temperature",
+            InputPair::HmassT => "Mass enthalpy and temperature",
+            InputPair::TUmolar => "Temperature and molar internal energy",
+            InputPair::TUmass => "Temperature and mass internal energy",
+        }
+    }
+
+    /// The pair that supplies `a` and `b`, in either order, or `None` if CoolProp has no such
+    /// pair.
+    ///
+    /// This is the inverse of [`params`](Self::params): both `try_from_params(Param::P, Param::T)`
+    /// and `try_from_params(Param::T, Param::P)` give `Some(InputPair::PT)`. Compare the result's
+    /// `params()` with `(a, b)` to learn whether the values need swapping.
+    pub fn try_from_params(a: Param, b: Param) -> Option<InputPair> {
+        InputPair::ALL.iter().copied().find(|pair| {
+            let (first, second) = pair.params();
+            (first, second) == (a, b) || (first, second) == (b, a)
+        })
+    }
+
+    /// SI units of the two input values, in the order they are passed to `update`.
+    pub(crate) fn units(self) -> (&'static str, &'static str) {
+        const Q: &str = "mol/mol";
+        match self {
+            InputPair::PT => ("Pa", "K"),
+            InputPair::QT => (Q, "K"),
+            InputPair::PQ => ("Pa", Q),
+            InputPair::QSmolar => (Q, "J/(mol*K)"),
+            InputPair::QSmass => (Q, "J/(kg*K)"),
+            InputPair::HmolarQ => ("J/mol", Q),
+            InputPair::HmassQ => ("J/kg", Q),
+            InputPair::DmolarQ => ("mol/m^3", Q),
+            InputPair::DmassQ => ("kg/m^3", Q),
+            InputPair::HmolarP => ("J/mol", "Pa"),
+            InputPair::HmassP => ("J/kg", "Pa"),
+            InputPair::PSmolar => ("Pa", "J/(mol*K)"),
+            InputPair::PSmass => ("Pa", "J/(kg*K)"),
+            InputPair::PUmolar => ("Pa", "J/mol"),
+            InputPair::PUmass => ("Pa", "J/kg"),
+            InputPair::HmolarSmolar => ("J/mol", "J/(mol*K)"),
+            InputPair::HmassSmass => ("J/kg", "J/(kg*K)"),
+            InputPair::SmolarT => ("J/(mol*K)", "K"),
+            InputPair::SmassT => ("J/(kg*K)", "K"),
+            InputPair::DmolarT => ("mol/m^3", "K"),
+            InputPair::DmassT => ("kg/m^3", "K"),
+            InputPair::DmolarP => ("mol/m^3", "Pa"),
+            InputPair::DmassP => ("kg/m^3", "Pa"),
+            InputPair::DmolarHmolar => ("mol/m^3", "J/mol"),
+            InputPair::DmassHmass => ("kg/m^3", "J/kg"),
+            InputPair::DmolarSmolar => ("mol/m^3", "J/(mol*K)"),
+            InputPair::DmassSmass => ("kg/m^3", "J/(kg*K)"),
+            InputPair::DmolarUmolar => ("mol/m^3", "J/mol"),
+            InputPair::DmassUmass => ("kg/m^3", "J/kg"),
+            InputPair::HmolarT => ("J/mol", "K"),
+            InputPair::HmassT => ("J/kg", "K"),
+            InputPair::TUmolar => ("K", "J/mol"),
+            InputPair::TUmass => ("K", "J/kg"),
+        }
+    }
+}
+
 macro_rules! coolprop_params {
     ($( $variant:ident => $name:literal ),+ $(,)?) => {
         #[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
@@ -286,6 +486,109 @@ coolprop_params! {
     Smass0 => "Smass_idealgas",
 }
 
+impl Param {
+    /// Whether the parameter is state-independent ("trivial" in CoolProp's parameter model).
+    ///
+    /// Trivial parameters are fluid constants such as critical and triple-point values, limits,
+    /// molar mass, and environmental indices. They can be queried from an
+    /// [`AbstractState`](crate::AbstractState) before any `update`; every other parameter needs a
+    /// thermodynamic state.
+    pub fn is_trivial(self) -> bool {
+        matches!(
+            self,
+            Param::Gwp20
+                | Param::Gwp100
+                | Param::Gwp500
+                | Param::Fh
+                | Param::Hh
+                | Param::Ph
+                | Param::Odp
+                | Param::GasConstant
+                | Param::MolarMass
+                | Param::Acentric
+                | Param::DipoleMoment
+                | Param::RhomassReducing
+                | Param::RhomolarReducing
+                | Param::RhomolarCritical
+                | Param::RhomassCritical
+                | Param::TReducing
+                | Param::TCritical
+                | Param::TTriple
+                | Param::TMax
+                | Param::TMin
+                | Param::PMin
+                | Param::PMax
+                | Param::PCritical
+                | Param::PReducing
+                | Param::PTriple
+                | Param::FractionMin
+                | Param::FractionMax
+                | Param::TFreeze
+        )
+    }
+
+    /// SI units of the parameter as reported by CoolProp, for example `"Pa"` for [`Param::P`].
+    ///
+    /// Dimensionless parameters report `"-"`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if CoolProp does not know the parameter or cannot describe it.
+    pub fn units(self) -> Result<String> {
+        self.information("units")
+    }
+
+    /// One-line description of the parameter from CoolProp, for example `"Pressure"` for
+    /// [`Param::P`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if CoolProp does not know the parameter or cannot describe it.
+    pub fn description(self) -> Result<String> {
+        self.information("long")
+    }
+
+    /// Query `get_parameter_information_string` for one information key (`"units"`, `"long"`,
+    /// `"short"` or `"IO"`).
+    fn information(self, key: &str) -> Result<String> {
+        let context = format!(
+            "get_parameter_information_string({}, {key})",
+            self.as_coolprop_str()
+        );
+        if global_indices()?.id_of_param(self) < 0 {
+            return Err(Error::InvalidInput(format!(
+                "{context}: parameter is not known to this CoolProp build"
+            )));
+        }
+        parameter_information(self.as_coolprop_cstr(), key, &context)
+    }
+}
+
+/// Query `get_parameter_information_string` for the parameter `name`, which may be any name or
+/// alias CoolProp accepts. `context` labels errors.
+pub(crate) fn parameter_information(name: &CStr, key: &str, context: &str) -> Result<String> {
+    // CoolProp reads the information key from the output buffer before overwriting it.
+    let mut buffer = [0 as c_char; 256];
+    if key.len() >= buffer.len() {
+        return Err(Error::InvalidInput(format!("{context}: key is too long")));
+    }
+    for (slot, &byte) in buffer.iter_mut().zip(key.as_bytes()) {
+        *slot = byte as c_char;
+    }
+    let status = unsafe {
+        crate::ffi::get_parameter_information_string(
+            name.as_ptr(),
+            buffer.as_mut_ptr(),
+            buffer.len() as c_int,
+        )
+    };
+    if status != 1 {
+        return Err(crate::coolprop_global_error(context));
+    }
+    buffer[buffer.len() - 1] = 0;
+    Ok(crate::c_buf_to_string(&buffer))
+}
+
 pub(crate) struct Indices {
     input_pair_ids: Box<[c_long]>,
     param_ids: Box<[c_long]>,
@@ -337,20 +640,22 @@ impl Indices {
 
 static INDICES: OnceLock<Indices> = OnceLock::new();
 
+/// Parameter and input-pair indices, loaded on first use.
+///
+/// The first call also issues one trivial `PropsSI` query so CoolProp loads its fluid database
+/// exactly once. Initialization runs inside `OnceLock::get_or_init`, so threads racing on the
+/// first `AbstractState` block until it finishes instead of each triggering their own load.
 pub(crate) fn global_indices() -> Result<&'static Indices> {
-    if let Some(indices) = INDICES.get() {
-        return Ok(indices);
-    }
-    let computed = Indices::load();
-    match INDICES.set(computed) {
-        Ok(_) => Ok(INDICES.get().expect("CoolProp indices initialized")),
-        Err(_) => Ok(INDICES.get().expect("CoolProp indices initialized")),
-    }
+    Ok(INDICES.get_or_init(|| {
+        // The value is irrelevant; a failure here resurfaces on the caller's own query.
+        let _ = crate::props_si("P", "T", 300.0, "Q", 0.0, "Water");
+        Indices::load()
+    }))
 }
 
 #[cfg(test)]
 mod tests {
-    use super::Phase;
+    use super::{InputPair, Param, Phase};
 
     #[test]
     fn phase_from_code_and_tokens() {
@@ -378,4 +683,86 @@ mod tests {
         assert_eq!(Phase::Liquid.to_string(), "liquid");
         assert_eq!(Phase::TwoPhase.to_string(), "two-phase");
     }
+
+    #[test]
+    fn phase_all_follows_code_order() {
+        assert_eq!(Phase::ALL.len(), 9);
+        for (code, &phase) in Phase::ALL.iter().enumerate() {
+            assert_eq!(Phase::from_code(code as i32), Some(phase));
+        }
+    }
+
+    #[test]
+    fn phase_codes_and_labels_round_trip() {
+        for &phase in Phase::ALL {
+            assert_eq!(Phase::from_code(phase.as_code()), Some(phase));
+            assert_eq!(phase.to_string().parse::<Phase>().unwrap(), phase);
+            assert_eq!(phase.specifier_token().parse::<Phase>().unwrap(), phase);
+        }
+        assert_eq!("twophase".parse::<Phase>().unwrap(), Phase::TwoPhase);
+        assert_eq!(
+            " Supercritical_Gas ".parse::<Phase>().unwrap(),
+            Phase::SupercriticalGas
+        );
+        assert!("plasma".parse::<Phase>().is_err());
+        assert!("phase_".parse::<Phase>().is_err());
+        assert_eq!("PHASE_GAS".parse::<Phase>().unwrap(), Phase::Gas);
+    }
+
+    #[test]
+    fn trivial_params_are_fluid_constants() {
+        assert!(Param::TCritical.is_trivial());
+        assert!(Param::MolarMass.is_trivial());
+        assert!(Param::Gwp100.is_trivial());
+        assert!(!Param::T.is_trivial());
+        assert!(!Param::Hmass.is_trivial());
+        assert!(!Param::Phase.is_trivial());
+        assert_eq!(Param::ALL.iter().filter(|p| p.is_trivial()).count(), 28);
+    }
+
+    #[test]
+    fn input_pair_descriptions_are_distinct() {
+        assert_eq!(InputPair::PT.description(), "Pressure and temperature");
+        assert_eq!(
+            InputPair::HmassP.description(),
+            "Mass enthalpy and pressure"
+        );
+        let mut descriptions: Vec<_> = InputPair::ALL.iter().map(|p| p.description()).collect();
+        descriptions.sort_unstable();
+        descriptions.dedup();
+        assert_eq!(descriptions.len(), InputPair::ALL.len());
+    }
+
+    #[test]
+    fn input_pairs_resolve_from_params_in_either_order() {
+        for &pair in InputPair::ALL {
+            let (first, second) = pair.params();
+            assert_eq!(InputPair::try_from_params(first, second), Some(pair));
+            assert_eq!(InputPair::try_from_params(second, first), Some(pair));
+        }
+        assert_eq!(
+            InputPair::try_from_params(Param::T, Param::Dmolar),
+            Some(InputPair::DmolarT)
+        );
+        assert_eq!(
+            InputPair::try_from_params(Param::Cpmass, Param::Viscosity),
+            None
+        );
+        assert_eq!(InputPair::try_from_params(Param::T, Param::T), None);
+    }
+
+    #[test]
+    fn input_pair_params_spell_the_pair_token() {
+        for &pair in InputPair::ALL {
+            let (first, second) = pair.params();
+            assert_eq!(
+                format!(
+                    "{}{}_INPUTS",
+                    first.as_coolprop_str(),
+                    second.as_coolprop_str()
+                ),
+                pair.as_coolprop_str()
+            );
+        }
+    }
 }
@@ -12,19 +12,39 @@ mod error;
 mod ha_props;
 mod indices;
 mod props;
+mod saturation;
+#[cfg(feature = "uom")]
+mod units;
 
 use std::{
+    collections::HashMap,
     ffi::{CStr, CString, c_char},
-    path::Path,
+    path::{Path, PathBuf},
+    sync::{Mutex, OnceLock, PoisonError},
 };
 
 pub use abstract_state::{
-    AbstractState, BatchCommonOutputs, CriticalPoint, PhaseEnvelope, SpinodalCurve,
+    AbstractState, BatchCommonOutputs, ComponentIndex, CriticalPoint, HelmholtzDerivs,
+    PhaseEnvelope, PropertyStream, SpinodalCurve, UpdateStats, critical_locus, enthalpy_difference,
+    entropy_generation, fluid_constants_table, phase_envelopes_over_compositions, phase_grid,
+    property_grid,
 };
 pub use error::{Error, Result};
-pub use ha_props::ha_props_si;
+pub use ha_props::{
+    HumidAirState, STANDARD_ATMOSPHERE, adiabatic_saturation_temperature, dew_or_frost_point,
+    ha_props_si, ha_props_si_atm, mix_airstreams, moist_air_density, moist_air_density_dry_basis,
+    moist_air_specific_heat, sensible_latent_split,
+};
 pub use indices::{InputPair, Param, Phase};
-pub use props::{props_si, props1_si};
+pub use props::{
+    Derivative, MixtureSpec, critical_compressibility, enthalpy_at_pq, normal_boiling_point,
+    parse_mixture_spec, props_ksi, props_si, props_si_array, props_si_derivative, props_si_incomp,
+    props_si_multi, props_si_multi_components, props_si_verbose, props1_si, sensible_heat,
+    triple_point,
+};
+pub use saturation::{SatRow, SaturationCurve, SaturationTable};
+#[cfg(feature = "uom")]
+pub use units::{SiQuantity, props_si_uom};
 
 pub(crate) fn check_finite_and_report_error(value: f64, context: &str) -> Result<f64> {
     if value.is_finite() {
@@ -48,6 +68,83 @@ pub(crate) fn c_buf_to_string(buf: &[c_char]) -> String {
     }
 }
 
+/// Strict counterpart of [`c_buf_to_string`] that rejects invalid UTF-8 instead of replacing it.
+pub(crate) fn try_c_buf_to_string(buf: &[c_char]) -> Result<String> {
+    let bytes = unsafe { std::slice::from_raw_parts(buf.as_ptr().cast::<u8>(), buf.len()) };
+    let bytes = match CStr::from_bytes_until_nul(bytes) {
+        Ok(cstr) => cstr.to_bytes(),
+        Err(_) => bytes,
+    };
+    std::str::from_utf8(bytes)
+        .map(str::to_owned)
+        .map_err(Error::InvalidUtf8)
+}
+
+/// Sizing policy for FFI calls that write into a caller-provided buffer and have to be retried
+/// with a larger one when the output does not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BufferGrowth {
+    /// Capacity, in elements, of the first attempt.
+    pub(crate) initial: usize,
+    /// Multiplier applied to the capacity after each attempt that did not fit; values below 2
+    /// are treated as 2.
+    pub(crate) factor: usize,
+    /// Largest capacity attempted before giving up.
+    pub(crate) max: usize,
+}
+
+impl BufferGrowth {
+    pub(crate) const fn new(initial: usize) -> Self {
+        Self {
+            initial,
+            factor: 2,
+            max: 1 << 24,
+        }
+    }
+
+    /// Capacity to try after `capacity` was too small, honouring a size the callee reported as
+    /// `needed` (`0` when unknown). `None` once the limit has been reached.
+    pub(crate) fn next(&self, capacity: usize, needed: usize) -> Option<usize> {
+        if capacity >= self.max {
+            return None;
+        }
+        let grown = capacity.max(1).saturating_mul(self.factor.max(2));
+        Some(grown.max(needed).min(self.max))
+    }
+}
+
+/// Outcome of one attempt inside [`grow_buffer`].
+pub(crate) enum Fill<T> {
+    /// The output fit; stop retrying.
+    Done(T),
+    /// The buffer was too small. Carries the required capacity if the callee reported one, or `0`.
+    Grow(usize),
+}
+
+/// Call `attempt` with increasing capacities, starting at `growth.initial`, until it reports
+/// [`Fill::Done`] or fails.
+///
+/// Returns a [`Error::Computation`] if the output still does not fit at `growth.max`.
+pub(crate) fn grow_buffer<T>(
+    growth: BufferGrowth,
+    mut attempt: impl FnMut(usize) -> Result<Fill<T>>,
+) -> Result<T> {
+    let mut capacity = growth.initial.clamp(1, growth.max.max(1));
+    loop {
+        match attempt(capacity)? {
+            Fill::Done(value) => return Ok(value),
+            Fill::Grow(needed) => {
+                capacity = growth
+                    .next(capacity, needed)
+                    .ok_or_else(|| Error::Computation {
+                        context: "buffer growth".into(),
+                        message: format!("output does not fit in {} elements", growth.max),
+                    })?;
+            }
+        }
+    }
+}
+
 pub(crate) fn coolprop_global_error(context: &str) -> Error {
     let message = global_param_string("errstring").unwrap_or_else(|_| "unknown error".into());
     Error::CoolPropGlobalError {
@@ -69,6 +166,7 @@ pub(crate) fn coolprop_global_error(context: &str) -> Error {
 /// - `"incompressible_list_solution"`: List of incompressible solutions/brines
 /// - `"REFPROP_version"`: REFPROP version (if available)
 /// - `"errstring"`: Most recent error message from CoolProp
+/// - `"warnstring"`: Most recent warning message from CoolProp
 ///
 /// # Examples
 ///
@@ -95,45 +193,179 @@ pub(crate) fn coolprop_global_error(context: &str) -> Error {
 /// - The requested parameter does not exist or cannot be retrieved
 /// - The buffer size is insufficient (automatically retried up to 1 MB)
 pub fn global_param_string(param: &str) -> Result<String> {
-    let key = CString::new(param).map_err(|source| Error::EmbeddedNul {
-        label: "param",
-        source,
-    })?;
-    let err_key = CString::new("errstring").expect("static string");
+    GlobalParamReader::new().read(param)
+}
 
-    let mut capacity: usize = 256;
-    loop {
-        let mut buffer = vec![0 as c_char; capacity];
-        let status = unsafe {
-            (ffi::get_global_param_string)(key.as_ptr(), buffer.as_mut_ptr(), capacity as i32)
-        };
-        if status == 1 {
-            // Protect against non-terminated writes from the C side.
-            buffer[capacity - 1] = 0;
-            let value = c_buf_to_string(&buffer);
-            return Ok(value);
+/// Reads CoolProp global parameter strings through one reusable buffer.
+///
+/// [`global_param_string`] allocates a fresh buffer on every call. A reader keeps its buffer
+/// between reads, and keeps any growth needed for long values such as `"FluidsList"`, so code
+/// that polls parameters repeatedly, such as `"errstring"` after each call, does not allocate a
+/// buffer each time. Only the returned `String` is allocated per read. The buffer is allocated
+/// on the first read, so [`new`](Self::new) is `const` and a reader can live in a `static`.
+///
+/// # Examples
+///
+/// ```rust
+/// use coolprop::GlobalParamReader;
+///
+/// # fn main() -> coolprop::Result<()> {
+/// # if cfg!(cp_docs_rs) { return Ok(()); }
+/// let mut reader = GlobalParamReader::new();
+/// let version = reader.read("version")?;
+/// let pending_error = reader.read("errstring")?;
+/// println!("CoolProp {version}, last error: {pending_error:?}");
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone)]
+pub struct GlobalParamReader {
+    buffer: Vec<c_char>,
+}
+
+impl GlobalParamReader {
+    const GROWTH: BufferGrowth = BufferGrowth {
+        max: 1 << 20,
+        ..BufferGrowth::new(256)
+    };
+
+    /// Create a reader; its buffer starts at 256 bytes on the first read.
+    pub const fn new() -> Self {
+        Self { buffer: Vec::new() }
+    }
+
+    /// Read a global parameter string; see [`global_param_string`] for the available keys.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`global_param_string`]. The buffer grows by doubling up to 1 MB, and only while
+    /// CoolProp reports it as too small: any other failure, such as an unknown key, is returned
+    /// straight away.
+    pub fn read(&mut self, param: &str) -> Result<String> {
+        let key = CString::new(param).map_err(|source| Error::EmbeddedNul {
+            label: "param",
+            source,
+        })?;
+        if self.buffer.is_empty() {
+            self.buffer.resize(Self::GROWTH.initial, 0);
         }
-        if capacity >= (1 << 20) {
-            let mut err_buf = vec![0 as c_char; 1024];
-            unsafe {
+        loop {
+            let capacity = self.buffer.len();
+            let status = unsafe {
                 (ffi::get_global_param_string)(
-                    err_key.as_ptr(),
-                    err_buf.as_mut_ptr(),
-                    err_buf.len() as i32,
-                );
+                    key.as_ptr(),
+                    self.buffer.as_mut_ptr(),
+                    capacity as i32,
+                )
+            };
+            if status == 1 {
+                // Protect against non-terminated writes from the C side.
+                self.buffer[capacity - 1] = 0;
+                return Ok(c_buf_to_string(&self.buffer));
+            }
+            let message = take_errstring();
+            match Self::GROWTH.next(capacity, 0) {
+                Some(grown) if reports_buffer_too_small(&message) => self.buffer.resize(grown, 0),
+                _ => {
+                    return Err(Error::GlobalParameter {
+                        param: param.to_string(),
+                        message,
+                    });
+                }
             }
-            let err_len = err_buf.len();
-            err_buf[err_len - 1] = 0;
-            let message = c_buf_to_string(&err_buf);
-            return Err(Error::GlobalParameter {
-                param: param.to_string(),
-                message,
-            });
         }
-        capacity *= 2;
     }
 }
 
+/// Whether `message` is the error CoolProp's C API records when an output does not fit the
+/// caller's buffer ("Buffer size is too small", with the required size in newer releases).
+fn reports_buffer_too_small(message: &str) -> bool {
+    message.to_ascii_lowercase().contains("too small")
+}
+
+/// Read and clear CoolProp's error string through a fixed 1 KiB buffer, without retrying.
+fn take_errstring() -> String {
+    let err_key = c"errstring";
+    let mut err_buf = [0 as c_char; 1024];
+    unsafe {
+        (ffi::get_global_param_string)(
+            err_key.as_ptr(),
+            err_buf.as_mut_ptr(),
+            err_buf.len() as i32,
+        );
+    }
+    err_buf[err_buf.len() - 1] = 0;
+    c_buf_to_string(&err_buf)
+}
+
+impl Default for GlobalParamReader {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Take CoolProp's pending warning message, if any.
+///
+/// CoolProp records non-fatal problems in a warning string separate from the error string, via
+/// the `"warnstring"` global parameter. Reading it clears it, so each warning is returned once;
+/// call this right after a calculation to attribute the warning to it. Warnings are global to
+/// the process, so concurrent calls on other threads can interleave theirs. Only conditions
+/// CoolProp explicitly reports are captured: many backends clamp or extrapolate silently, so
+/// `None` does not prove a result lies within a model's validity range.
+pub fn last_warning() -> Option<String> {
+    global_param_string("warnstring")
+        .ok()
+        .filter(|warning| !warning.is_empty())
+}
+
+/// Description of the linked CoolProp build, as returned by [`build_info`].
+///
+/// With the `serde` feature the struct can be serialized, for example into bug reports.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildInfo {
+    /// CoolProp version string (`"version"`).
+    pub version: String,
+    /// Git commit hash CoolProp was built from (`"gitrevision"`).
+    pub git_revision: String,
+    /// Version of the REFPROP library CoolProp can load, or `None` if REFPROP is unavailable.
+    pub refprop_version: Option<String>,
+    /// Number of fluids in `"FluidsList"`, or `None` if the list cannot be read.
+    pub fluid_count: Option<usize>,
+}
+
+/// Collect the version, git revision, REFPROP version, and fluid count of the linked CoolProp
+/// build in one call.
+///
+/// REFPROP is optional: when CoolProp cannot report its version (an error, an empty string, or
+/// `"n/a"`), `refprop_version` is `None` rather than an error. Querying it may make CoolProp
+/// try to load the REFPROP library.
+///
+/// # Errors
+///
+/// Returns an error only if `"version"` or `"gitrevision"` cannot be read.
+pub fn build_info() -> Result<BuildInfo> {
+    let mut reader = GlobalParamReader::new();
+    let version = reader.read("version")?;
+    let git_revision = reader.read("gitrevision")?;
+    let refprop_version = reader
+        .read("REFPROP_version")
+        .ok()
+        .map(|version| version.trim().to_owned())
+        .filter(|version| !version.is_empty() && version != "n/a");
+    let fluid_count = reader.read("FluidsList").ok().map(|list| {
+        list.split(',')
+            .filter(|name| !name.trim().is_empty())
+            .count()
+    });
+    Ok(BuildInfo {
+        version,
+        git_revision,
+        refprop_version,
+        fluid_count,
+    })
+}
+
 /// Retrieve a high-level fluid metadata field using CoolProp `get_fluid_param_string`.
 pub fn fluid_param_string(fluid: &str, param: &str) -> Result<String> {
     let fluid_c = CString::new(fluid).map_err(|source| Error::EmbeddedNul {
@@ -151,8 +383,11 @@ pub fn fluid_param_string(fluid: &str, param: &str) -> Result<String> {
         return Err(coolprop_global_error(&context));
     }
 
-    let mut capacity = (required_len as usize + 1).max(256);
-    loop {
+    let growth = BufferGrowth {
+        max: 1 << 20,
+        ..BufferGrowth::new((required_len as usize + 1).max(256))
+    };
+    grow_buffer(growth, |capacity| {
         let mut buffer = vec![0 as c_char; capacity];
         let status = unsafe {
             ffi::get_fluid_param_string(
@@ -164,13 +399,97 @@ pub fn fluid_param_string(fluid: &str, param: &str) -> Result<String> {
         };
         if status == 1 {
             buffer[capacity - 1] = 0;
-            return Ok(c_buf_to_string(&buffer));
+            return Ok(Fill::Done(c_buf_to_string(&buffer)));
         }
-        if capacity >= (1 << 20) {
+        if capacity >= growth.max {
             return Err(coolprop_global_error(&context));
         }
-        capacity *= 2;
+        Ok(Fill::Grow(0))
+    })
+}
+
+/// Alternative names CoolProp accepts for `fluid`, parsed from the comma-separated `aliases`
+/// field of [`fluid_param_string`].
+///
+/// Surrounding whitespace is trimmed and empty entries are dropped, so a fluid without aliases
+/// yields an empty vector. The canonical name itself is not included.
+pub fn fluid_aliases(fluid: &str) -> Result<Vec<String>> {
+    Ok(fluid_param_string(fluid, "aliases")?
+        .split(',')
+        .map(str::trim)
+        .filter(|alias| !alias.is_empty())
+        .map(str::to_owned)
+        .collect())
+}
+
+static FLUID_NAMES: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Lowercased fluid names and aliases mapped to canonical names, built on first use.
+///
+/// Canonical names are inserted before any alias so they win if another fluid happens to list
+/// the same string as an alias.
+fn fluid_name_map() -> Result<&'static HashMap<String, String>> {
+    if let Some(map) = FLUID_NAMES.get() {
+        return Ok(map);
+    }
+    let fluids = global_param_string("FluidsList")?;
+    let fluids: Vec<&str> = fluids.split(',').map(str::trim).collect();
+    let mut map = HashMap::new();
+    for &fluid in &fluids {
+        map.insert(fluid.to_lowercase(), fluid.to_owned());
     }
+    for &fluid in &fluids {
+        for alias in fluid_aliases(fluid)? {
+            map.entry(alias.to_lowercase())
+                .or_insert_with(|| fluid.to_owned());
+        }
+    }
+    Ok(FLUID_NAMES.get_or_init(|| map))
+}
+
+/// Canonical CoolProp name of a fluid given by its name or any of its aliases.
+///
+/// Matching ignores case and surrounding whitespace, so `"co2"`, `"R744"`, and
+/// `"CarbonDioxide"` all resolve to `"CarbonDioxide"`. The alias map covers every fluid in
+/// CoolProp's `FluidsList` and is built once, on the first call. Backend prefixes and mixture
+/// specifications are not understood; pass a single fluid name.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if no fluid has `input` as its name or an alias, and
+/// propagates errors from reading the fluid list or alias metadata while building the map.
+pub fn canonical_fluid_name(input: &str) -> Result<String> {
+    fluid_name_map()?
+        .get(&input.trim().to_lowercase())
+        .cloned()
+        .ok_or_else(|| Error::InvalidInput(format!("unknown fluid name or alias: {input}")))
+}
+
+/// Fluids this crate guarantees to be available through the `HEOS` backend of any CoolProp build
+/// it links against.
+///
+/// The list is a curated subset of CoolProp's `FluidsList` and is verified by the crate's test
+/// suite, so downstream tests can iterate it without hardcoding names that a minimal build might
+/// lack. Names are CoolProp identifiers accepted by [`AbstractState::new`] and [`props_si`].
+pub fn known_fluids() -> &'static [&'static str] {
+    &[
+        "Water",
+        "Air",
+        "Nitrogen",
+        "Oxygen",
+        "Argon",
+        "Helium",
+        "Hydrogen",
+        "CarbonDioxide",
+        "Ammonia",
+        "Methane",
+        "Ethane",
+        "Propane",
+        "n-Butane",
+        "R32",
+        "R125",
+        "R134a",
+    ]
 }
 
 /// Determine phase as a short string label using CoolProp `PhaseSI`.
@@ -242,13 +561,74 @@ pub fn set_reference_state(fluid: &str, reference_state: &str) -> Result<()> {
     }
 }
 
+/// Run `f` with `fluid` switched to `reference_state`, then restore the default (`"DEF"`).
+///
+/// CoolProp keeps the reference state per fluid in global state and offers no way to read the
+/// current one back, so the fluid is always reset to its default afterwards, whether or not `f`
+/// succeeds. Like [`set_reference_state`], this is global and should not race with other
+/// CoolProp calls on the same fluid; states constructed inside `f` see the new reference.
+///
+/// # Errors
+///
+/// Returns the error from setting the reference state, otherwise the result of `f`. If `f`
+/// succeeds but restoring the default fails, that error is returned instead.
+pub fn with_reference_state<T, F>(fluid: &str, reference_state: &str, f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T>,
+{
+    set_reference_state(fluid, reference_state)?;
+    let result = f();
+    let reset = set_reference_state(fluid, "DEF");
+    let value = result?;
+    reset.map(|()| value)
+}
+
+/// Enthalpy and entropy offsets `(h_b - h_a, s_b - s_a)` between two reference states of a
+/// fluid, in J/kg and J/(kg*K).
+///
+/// A reference state only shifts the zero of `h` and `s`, so the offsets are the same at every
+/// state point; they are evaluated on the saturated liquid at temperature `t` (K), which must lie
+/// between the triple and critical temperatures. Add the offsets to values computed with
+/// `reference_a` to express them in `reference_b`, for example when comparing against another
+/// tool. Each reference is applied through [`with_reference_state`], so the fluid is left at its
+/// default reference afterwards.
+///
+/// # Errors
+///
+/// Propagates errors from setting either reference state or evaluating the saturated state.
+pub fn reference_offset(
+    fluid: &str,
+    t: f64,
+    reference_a: &str,
+    reference_b: &str,
+) -> Result<(f64, f64)> {
+    let saturated_liquid = || {
+        Ok((
+            props_si("Hmass", "T", t, "Q", 0.0, fluid)?,
+            props_si("Smass", "T", t, "Q", 0.0, fluid)?,
+        ))
+    };
+    let (h_a, s_a) = with_reference_state(fluid, reference_a, saturated_liquid)?;
+    let (h_b, s_b) = with_reference_state(fluid, reference_b, saturated_liquid)?;
+    Ok((h_b - h_a, s_b - s_a))
+}
+
+/// Reader used by [`config_call`] to poll `"errstring"`, kept so its buffer is reused.
+static ERRSTRING_READER: Mutex<GlobalParamReader> = Mutex::new(GlobalParamReader::new());
+
 fn config_call<F>(action: F, context: &str) -> Result<()>
 where
     F: FnOnce(),
 {
-    let _ = global_param_string("errstring");
+    let read_errstring = || {
+        ERRSTRING_READER
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .read("errstring")
+    };
+    let _ = read_errstring();
     action();
-    match global_param_string("errstring") {
+    match read_errstring() {
         Ok(after) if after.is_empty() => Ok(()),
         Ok(after) => Err(Error::CoolPropGlobalError {
             message: format!("{context}: {after}"),
@@ -311,6 +691,10 @@ pub fn set_config_string(key: &str, value: &str) -> Result<()> {
 }
 
 /// Get a boolean configuration value by key.
+///
+/// Uses CoolProp's `get_config_bool` when the linked library exports it. Otherwise the key is
+/// read from the configuration JSON reported by [`config_as_json`], and a key missing from the
+/// JSON is reported as [`Error::InvalidInput`].
 pub fn get_config_bool(key: &str) -> Result<bool> {
     let key_c = CString::new(key).map_err(|source| Error::EmbeddedNul {
         label: "config key",
@@ -329,13 +713,18 @@ pub fn get_config_bool(key: &str) -> Result<bool> {
     #[cfg(not(coolprop_has_get_config_bool))]
     {
         let _ = key_c;
-        Err(Error::InvalidInput(
-            "this CoolProp build does not expose get_config_bool".into(),
-        ))
+        match config_json_value(key)? {
+            ConfigValue::Bool(value) => Ok(value),
+            _ => Err(Error::InvalidInput(format!(
+                "config key {key} is not a boolean"
+            ))),
+        }
     }
 }
 
 /// Get a floating-point configuration value by key.
+///
+/// Falls back to the configuration JSON like [`get_config_bool`].
 pub fn get_config_double(key: &str) -> Result<f64> {
     let key_c = CString::new(key).map_err(|source| Error::EmbeddedNul {
         label: "config key",
@@ -354,13 +743,18 @@ pub fn get_config_double(key: &str) -> Result<f64> {
     #[cfg(not(coolprop_has_get_config_double))]
     {
         let _ = key_c;
-        Err(Error::InvalidInput(
-            "this CoolProp build does not expose get_config_double".into(),
-        ))
+        match config_json_value(key)? {
+            ConfigValue::Number(value) => Ok(value),
+            _ => Err(Error::InvalidInput(format!(
+                "config key {key} is not a number"
+            ))),
+        }
     }
 }
 
 /// Get a string configuration value by key.
+///
+/// Falls back to the configuration JSON like [`get_config_bool`].
 pub fn get_config_string(key: &str) -> Result<String> {
     let key_c = CString::new(key).map_err(|source| Error::EmbeddedNul {
         label: "config key",
@@ -387,12 +781,189 @@ pub fn get_config_string(key: &str) -> Result<String> {
     #[cfg(not(coolprop_has_get_config_string))]
     {
         let _ = key_c;
+        match config_json_value(key)? {
+            ConfigValue::String(value) => Ok(value),
+            _ => Err(Error::InvalidInput(format!(
+                "config key {key} is not a string"
+            ))),
+        }
+    }
+}
+
+/// The whole CoolProp configuration as a JSON object, one entry per key.
+///
+/// Useful for test fixtures that need to record the configuration and restore it afterwards.
+/// Wraps CoolProp's `get_config_as_json_string`.
+///
+/// # Errors
+///
+/// Returns an error if CoolProp cannot report its configuration, and [`Error::InvalidInput`] if
+/// the linked CoolProp does not export `get_config_as_json_string`.
+pub fn config_as_json() -> Result<String> {
+    #[cfg(coolprop_has_get_config_as_json_string)]
+    {
+        let growth = BufferGrowth {
+            max: 1 << 20,
+            ..BufferGrowth::new(4096)
+        };
+        grow_buffer(growth, |capacity| {
+            let mut buffer = vec![0 as c_char; capacity];
+            let status =
+                unsafe { ffi::get_config_as_json_string(buffer.as_mut_ptr(), capacity as i32) };
+            if status == 1 {
+                buffer[capacity - 1] = 0;
+                return Ok(Fill::Done(c_buf_to_string(&buffer)));
+            }
+            Ok(Fill::Grow(0))
+        })
+    }
+    #[cfg(not(coolprop_has_get_config_as_json_string))]
+    {
         Err(Error::InvalidInput(
-            "this CoolProp build does not expose get_config_string".into(),
+            "this CoolProp build does not expose get_config_as_json_string".into(),
         ))
     }
 }
 
+/// Overrides one configuration key and restores its previous value when dropped.
+///
+/// CoolProp configuration is process-global, so a test that changes a key leaks the change into
+/// every later test. A guard reads the current value with the matching `get_config_*` getter,
+/// applies the override, and puts the old value back on drop, also when the test panics.
+/// Restoring is best effort: an error from CoolProp during drop is ignored.
+///
+/// Like the setters, a guard must only be created or dropped while no other CoolProp call is
+/// running, for example under the same lock that serializes the tests.
+///
+/// # Examples
+///
+/// ```rust
+/// use coolprop::{ConfigGuard, get_config_bool};
+///
+/// # fn main() -> coolprop::Result<()> {
+/// # if cfg!(cp_docs_rs) { return Ok(()); }
+/// let before = get_config_bool("NORMALIZE_GAS_CONSTANTS")?;
+/// {
+///     let _guard = ConfigGuard::set_bool("NORMALIZE_GAS_CONSTANTS", !before)?;
+///     assert_eq!(get_config_bool("NORMALIZE_GAS_CONSTANTS")?, !before);
+/// }
+/// assert_eq!(get_config_bool("NORMALIZE_GAS_CONSTANTS")?, before);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug)]
+#[must_use = "the previous value is restored as soon as the guard is dropped"]
+pub struct ConfigGuard {
+    key: String,
+    previous: ConfigValue,
+}
+
+impl ConfigGuard {
+    /// Set the boolean `key` to `value` until the guard is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the current value cannot be read or the new one cannot be set; the
+    /// configuration is unchanged in that case.
+    pub fn set_bool(key: &str, value: bool) -> Result<Self> {
+        let previous = ConfigValue::Bool(get_config_bool(key)?);
+        set_config_bool(key, value)?;
+        Ok(Self::restoring(key, previous))
+    }
+
+    /// Set the floating-point `key` to `value` until the guard is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`set_bool`](Self::set_bool).
+    pub fn set_double(key: &str, value: f64) -> Result<Self> {
+        let previous = ConfigValue::Number(get_config_double(key)?);
+        set_config_double(key, value)?;
+        Ok(Self::restoring(key, previous))
+    }
+
+    /// Set the string `key` to `value` until the guard is dropped.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`set_bool`](Self::set_bool).
+    pub fn set_string(key: &str, value: &str) -> Result<Self> {
+        let previous = ConfigValue::String(get_config_string(key)?);
+        set_config_string(key, value)?;
+        Ok(Self::restoring(key, previous))
+    }
+
+    /// Set CoolProp's debug level to `level` until the guard is dropped.
+    ///
+    /// The debug level is not a configuration key, see [`set_debug_level`].
+    pub fn set_debug_level(level: i32) -> Self {
+        let previous = ConfigValue::DebugLevel(get_debug_level());
+        set_debug_level(level);
+        Self::restoring("debug_level", previous)
+    }
+
+    fn restoring(key: &str, previous: ConfigValue) -> Self {
+        Self {
+            key: key.to_owned(),
+            previous,
+        }
+    }
+}
+
+impl Drop for ConfigGuard {
+    fn drop(&mut self) {
+        let _ = match &self.previous {
+            ConfigValue::Bool(value) => set_config_bool(&self.key, *value),
+            ConfigValue::Number(value) => set_config_double(&self.key, *value),
+            ConfigValue::String(value) => set_config_string(&self.key, value),
+            ConfigValue::DebugLevel(level) => {
+                set_debug_level(*level);
+                Ok(())
+            }
+        };
+    }
+}
+
+/// Scalar value of a configuration key, as read from CoolProp's configuration JSON or one of
+/// the `get_config_*` getters.
+#[derive(Debug, Clone, PartialEq)]
+enum ConfigValue {
+    Bool(bool),
+    Number(f64),
+    String(String),
+    /// CoolProp's debug level, which lives outside the configuration keys.
+    DebugLevel(i32),
+}
+
+/// Look `key` up in the configuration JSON reported by [`config_as_json`].
+///
+/// Used by the `get_config_*` getters when the linked CoolProp does not export them.
+#[cfg(not(all(
+    coolprop_has_get_config_bool,
+    coolprop_has_get_config_double,
+    coolprop_has_get_config_string
+)))]
+fn config_json_value(key: &str) -> Result<ConfigValue> {
+    let json = config_as_json()?;
+    let config: serde_json::Map<String, serde_json::Value> =
+        serde_json::from_str(&json).map_err(|err| Error::Computation {
+            context: "config_as_json".into(),
+            message: format!("configuration is not a JSON object: {err}"),
+        })?;
+    match config.get(key) {
+        Some(serde_json::Value::Bool(value)) => Ok(ConfigValue::Bool(*value)),
+        Some(serde_json::Value::Number(value)) => value
+            .as_f64()
+            .map(ConfigValue::Number)
+            .ok_or_else(|| Error::InvalidInput(format!("config key {key} is out of range"))),
+        Some(serde_json::Value::String(value)) => Ok(ConfigValue::String(value.clone())),
+        Some(_) => Err(Error::InvalidInput(format!(
+            "config key {key} does not hold a scalar value"
+        ))),
+        None => Err(Error::InvalidInput(format!("unknown config key {key}"))),
+    }
+}
+
 /// Set a floating-point configuration parameter in CoolProp.
 ///
 /// Adjusts numerical tolerances and physical constants used in CoolProp calculations.
@@ -488,6 +1059,32 @@ pub fn set_config_bool(key: &str, value: bool) -> Result<()> {
     )
 }
 
+/// Set CoolProp's debug level; `0` disables debug output, higher values print more.
+///
+/// CoolProp keeps its debug switch outside the configuration keys, so it is set here rather
+/// than through [`set_config_bool`]. Like the config setters, this changes process-global state.
+pub fn set_debug_level(level: i32) {
+    unsafe { ffi::set_debug_level(level) }
+}
+
+/// CoolProp's current debug level, as set with [`set_debug_level`].
+pub fn get_debug_level() -> i32 {
+    unsafe { ffi::get_debug_level() }
+}
+
+/// CODATA 2018 molar gas constant in J/(mol·K), CoolProp's default for `R_U_CODATA`.
+pub const R_U_DEFAULT: f64 = 8.314_462_618_153;
+
+/// Universal gas constant currently used by CoolProp, in J/(mol·K).
+///
+/// Reads the `R_U_CODATA` configuration value, so a constant changed with
+/// [`set_config_double`] is picked up here as well. If the key cannot be read, returns
+/// [`R_U_DEFAULT`]. Individual equations of state may carry their own fitted gas constant, which
+/// CoolProp only replaces with this one while `NORMALIZE_GAS_CONSTANTS` is enabled.
+pub fn universal_gas_constant() -> f64 {
+    get_config_double("R_U_CODATA").unwrap_or(R_U_DEFAULT)
+}
+
 /// Set the global path CoolProp uses to locate REFPROP files.
 ///
 /// This is a convenience wrapper around
@@ -499,3 +1096,296 @@ pub fn set_refprop_path<P: AsRef<Path>>(p: P) -> Result<()> {
         p.as_ref().to_string_lossy().as_ref(),
     )
 }
+
+/// Directory- and file-valued CoolProp configuration paths, applied together.
+///
+/// Each `Some` field maps to one configuration key; `None` fields are left untouched. Because
+/// CoolProp accepts any string for these keys and only fails later (or silently falls back) when
+/// the path is wrong, [`apply`](Self::apply) checks that every path exists before setting any.
+///
+/// # Examples
+///
+/// ```rust,no_run
+/// use coolprop::Paths;
+///
+/// # fn main() -> coolprop::Result<()> {
+/// Paths {
+///     refprop: Some("/opt/refprop".into()),
+///     ..Paths::default()
+/// }
+/// .apply()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Paths {
+    /// REFPROP installation directory (`ALTERNATIVE_REFPROP_PATH`), holding its `FLUIDS` and
+    /// `MIXTURES` folders.
+    pub refprop: Option<PathBuf>,
+    /// REFPROP shared library backing the `REFPROP` backend
+    /// (`ALTERNATIVE_REFPROP_LIBRARY_PATH`).
+    pub refprop_library: Option<PathBuf>,
+    /// Directory for the tabular backends' cached tables (`ALTERNATIVE_TABLES_DIRECTORY`).
+    pub tables_directory: Option<PathBuf>,
+}
+
+impl Paths {
+    /// Validate every configured path, then set the corresponding configuration keys.
+    ///
+    /// The same global-state caveats as [`set_config_string`] apply.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] naming the first path that does not exist, in which case no
+    /// key is changed, and otherwise any error from [`set_config_string`].
+    pub fn apply(&self) -> Result<()> {
+        let entries = [
+            ("ALTERNATIVE_REFPROP_PATH", &self.refprop),
+            ("ALTERNATIVE_REFPROP_LIBRARY_PATH", &self.refprop_library),
+            ("ALTERNATIVE_TABLES_DIRECTORY", &self.tables_directory),
+        ];
+        let entries: Vec<_> = entries
+            .into_iter()
+            .filter_map(|(key, path)| Some((key, path.as_deref()?)))
+            .collect();
+        if let Some((key, path)) = entries.iter().find(|(_, path)| !path.exists()) {
+            return Err(Error::InvalidInput(format!(
+                "{key} path does not exist: {}",
+                path.display()
+            )));
+        }
+        for (key, path) in entries {
+            set_config_string(key, path.to_string_lossy().as_ref())?;
+        }
+        Ok(())
+    }
+}
+
+/// Register user-defined fluids with a CoolProp backend from a JSON definition.
+///
+/// `json` holds a JSON array of fluid definitions in CoolProp's fluid-file format (the same
+/// format as the bundled `HEOS` fluids), and `backend` is typically `"HEOS"`. Registered fluids
+/// are available by name to [`AbstractState::new`] and [`props_si`] for the rest of the process.
+/// Like configuration changes, registration is global and should happen during initialization.
+/// This wraps CoolProp's `add_fluids_as_JSON`.
+///
+/// # Errors
+///
+/// Returns an error if either string contains a NUL byte, CoolProp rejects the JSON (for example
+/// a malformed definition or a fluid that is already registered), or the CoolProp build does not
+/// expose `add_fluids_as_JSON`.
+pub fn add_fluids_from_json(backend: &str, json: &str) -> Result<()> {
+    let backend_c = CString::new(backend).map_err(|source| Error::EmbeddedNul {
+        label: "backend",
+        source,
+    })?;
+    let json_c = CString::new(json).map_err(|source| Error::EmbeddedNul {
+        label: "fluid JSON",
+        source,
+    })?;
+    #[cfg(coolprop_has_add_fluids_as_json)]
+    {
+        abstract_state::call_with_error(|err, msg, len| unsafe {
+            ffi::add_fluids_as_JSON(backend_c.as_ptr(), json_c.as_ptr(), err, msg, len);
+        })
+    }
+    #[cfg(not(coolprop_has_add_fluids_as_json))]
+    {
+        let _ = (backend_c, json_c);
+        Err(Error::InvalidInput(
+            "this CoolProp build does not expose add_fluids_as_JSON".into(),
+        ))
+    }
+}
+
+/// Register mixture departure functions from a JSON definition.
+///
+/// `json` holds a JSON array of departure functions in the format of CoolProp's bundled
+/// mixture departure-function library. They apply to backends with multi-fluid mixture models,
+/// which in practice means `HEOS` mixtures, and are looked up by name from the binary
+/// interaction parameters when a mixture state is constructed. Register them, and any binary
+/// pairs referring to them, before calling [`AbstractState::new`] for the mixture.
+///
+/// CoolProp keeps departure functions in a process-wide library rather than per state, so this
+/// is a free function. Like [`add_fluids_from_json`], registration is global and should happen
+/// during initialization. This wraps CoolProp's `set_departure_functions`.
+///
+/// # Errors
+///
+/// Returns an error if `json` contains a NUL byte, CoolProp rejects the definition (its parse
+/// error is passed through unchanged as [`Error::CoolProp`]), or the CoolProp build does not
+/// expose `set_departure_functions`.
+pub fn set_departure_functions(json: &str) -> Result<()> {
+    let json_c = CString::new(json).map_err(|source| Error::EmbeddedNul {
+        label: "departure function JSON",
+        source,
+    })?;
+    #[cfg(coolprop_has_set_departure_functions)]
+    {
+        abstract_state::call_with_error(|err, msg, len| unsafe {
+            ffi::set_departure_functions(json_c.as_ptr(), err, msg, len);
+        })
+    }
+    #[cfg(not(coolprop_has_set_departure_functions))]
+    {
+        let _ = json_c;
+        Err(Error::InvalidInput(
+            "this CoolProp build does not expose set_departure_functions".into(),
+        ))
+    }
+}
+
+/// Build an [`AbstractState`] for a mixture with fixed mole fractions.
+///
+/// `mixture!("HEOS", Methane => 0.9, Ethane => 0.1)` is shorthand for
+/// [`AbstractState::new`] with the fluid string `"Methane&Ethane"` followed by
+/// [`set_fractions`](AbstractState::set_fractions) with `[0.9, 0.1]`, and evaluates to
+/// `Result<AbstractState>`. Components are identifiers; names that are not valid identifiers,
+/// such as `n-Butane`, need the explicit two-step setup.
+///
+/// Fractions must be float literals. Their sum (within `1e-9` of one) and sign are checked at
+/// compile time, so a typo in a static mixture fails the build instead of a later update.
+/// Component names are still resolved by CoolProp at run time.
+///
+/// # Examples
+///
+/// ```rust
+/// use coolprop::{InputPair, Param, mixture};
+///
+/// # fn main() -> coolprop::Result<()> {
+/// # if cfg!(cp_docs_rs) { return Ok(()); }
+/// let mut gas = mixture!("HEOS", Methane => 0.9, Ethane => 0.1)?;
+/// gas.update(InputPair::PT, 101_325.0, 300.0)?;
+/// assert!(gas.get(Param::Dmass)? > 0.0);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// ```compile_fail
+/// let gas = coolprop::mixture!("HEOS", Methane => 0.9, Ethane => 0.2);
+/// ```
+#[macro_export]
+macro_rules! mixture {
+    ($backend:expr, $($component:ident => $fraction:literal),+ $(,)?) => {{
+        const {
+            let sum = 0.0 $(+ $fraction)+;
+            assert!(
+                sum > 1.0 - 1e-9 && sum < 1.0 + 1e-9,
+                "mixture fractions must sum to 1"
+            );
+            $(assert!($fraction >= 0.0, "mixture fractions must not be negative");)+
+        }
+        let fluid = [$(stringify!($component)),+].join("&");
+        $crate::AbstractState::new($backend, &fluid).and_then(|mut state| {
+            state.set_fractions(&[$($fraction),+])?;
+            Ok(state)
+        })
+    }};
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        BufferGrowth, Fill, c_buf_to_string, grow_buffer, reports_buffer_too_small,
+        try_c_buf_to_string,
+    };
+    use crate::Error;
+    use std::ffi::c_char;
+
+    fn buf(bytes: &[u8]) -> Vec<c_char> {
+        bytes.iter().map(|&b| b as c_char).collect()
+    }
+
+    #[test]
+    fn strict_and_lossy_conversion_agree_on_ascii() {
+        let ascii = buf(b"HEOS\0garbage");
+        assert_eq!(c_buf_to_string(&ascii), "HEOS");
+        assert_eq!(try_c_buf_to_string(&ascii).unwrap(), "HEOS");
+
+        let unterminated = buf(b"R134a");
+        assert_eq!(try_c_buf_to_string(&unterminated).unwrap(), "R134a");
+    }
+
+    #[test]
+    fn strict_conversion_rejects_invalid_utf8() {
+        let invalid = buf(b"Wat\xffer\0");
+        assert_eq!(c_buf_to_string(&invalid), "Wat\u{fffd}er");
+        assert!(matches!(
+            try_c_buf_to_string(&invalid),
+            Err(Error::InvalidUtf8(_))
+        ));
+    }
+
+    #[test]
+    fn buffer_growth_multiplies_and_honours_reported_sizes() {
+        let growth = BufferGrowth {
+            initial: 4,
+            factor: 3,
+            max: 100,
+        };
+        assert_eq!(growth.next(4, 0), Some(12));
+        assert_eq!(growth.next(4, 50), Some(50));
+        assert_eq!(growth.next(40, 0), Some(100));
+        assert_eq!(growth.next(100, 0), None);
+        // The default doubles, a zero capacity still grows, and a factor below 2 is treated as
+        // doubling.
+        assert_eq!(BufferGrowth::new(4).next(4, 0), Some(8));
+        assert_eq!(
+            BufferGrowth {
+                factor: 1,
+                ..growth
+            }
+            .next(0, 0),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn buffer_too_small_is_told_apart_from_other_errors() {
+        assert!(reports_buffer_too_small("Buffer size is too small"));
+        assert!(reports_buffer_too_small(
+            "Buffer size is too small; must be at least 300 characters in size"
+        ));
+        assert!(!reports_buffer_too_small(
+            "Input parameter [__nope__] is invalid"
+        ));
+    }
+
+    #[test]
+    fn grow_buffer_retries_until_the_output_fits() {
+        let mut attempts = Vec::new();
+        let len = grow_buffer(BufferGrowth::new(16), |capacity| {
+            attempts.push(capacity);
+            Ok(if capacity < 100 {
+                Fill::Grow(0)
+            } else {
+                Fill::Done(capacity)
+            })
+        })
+        .unwrap();
+        assert_eq!(attempts, [16, 32, 64, 128]);
+        assert_eq!(len, 128);
+
+        let mut calls = 0;
+        let fits_first_time = grow_buffer(BufferGrowth::new(4096), |capacity| {
+            calls += 1;
+            Ok(Fill::Done(capacity))
+        });
+        assert_eq!((fits_first_time.unwrap(), calls), (4096, 1));
+    }
+
+    #[test]
+    fn grow_buffer_stops_at_the_limit_and_propagates_errors() {
+        let growth = BufferGrowth {
+            max: 64,
+            ..BufferGrowth::new(8)
+        };
+        let never_fits = grow_buffer(growth, |_| Ok(Fill::<()>::Grow(0)));
+        assert!(matches!(never_fits, Err(Error::Computation { .. })));
+
+        let failed = grow_buffer(growth, |_| -> crate::Result<Fill<()>> {
+            Err(Error::InvalidInput("boom".into()))
+        });
+        assert!(matches!(failed, Err(Error::InvalidInput(_))));
+    }
+}
@@ -4,7 +4,7 @@
 //! thermodynamic properties using CoolProp. It mirrors the `PropsSI` function from CoolProp's
 //! high-level API.
 
-use crate::{Error, Result, check_finite_and_report_error, ffi};
+use crate::{Error, Param, Result, check_finite_and_report_error, ffi, global_param_string};
 use std::ffi::CString;
 
 /// Calculate a thermodynamic property for a pure fluid or predefined mixture.
@@ -253,6 +253,114 @@ pub fn props_si(
     fluid: &str,
 ) -> Result<f64> {
     let context = format!("PropsSI({output}, {name1}={prop1}, {name2}={prop2}, {fluid})");
+    let value = props_si_raw(output, name1, prop1, name2, prop2, fluid)?;
+    check_finite_and_report_error(value, &context)
+}
+
+/// Calculate a property like [`props_si`], returning the raw value together with CoolProp's
+/// error string instead of a [`Result`].
+///
+/// The value is passed through unchecked, so it may be `NaN` or infinite. The message is the
+/// `errstring` global parameter read right after the call, or `None` if it is empty; any stale
+/// message is cleared before the call so it cannot be attributed to this one. Arguments
+/// containing an embedded NUL byte never reach CoolProp and yield `NaN` with a description of
+/// the problem. Intended for debugging and logging; use [`props_si`] in normal code.
+pub fn props_si_verbose(
+    output: &str,
+    name1: &str,
+    prop1: f64,
+    name2: &str,
+    prop2: f64,
+    fluid: &str,
+) -> (f64, Option<String>) {
+    // Reading the error string also resets it.
+    let _ = global_param_string("errstring");
+    match props_si_raw(output, name1, prop1, name2, prop2, fluid) {
+        Ok(value) => {
+            let message = global_param_string("errstring")
+                .ok()
+                .filter(|message| !message.is_empty());
+            (value, message)
+        }
+        Err(err) => (f64::NAN, Some(err.to_string())),
+    }
+}
+
+/// Evaluate [`props_si`] for each `(prop1[i], prop2[i])` pair, returning one value per pair.
+///
+/// The name and fluid strings are converted once for the whole batch instead of on every call,
+/// which matters when building tables with thousands of points.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if `prop1` and `prop2` differ in length, and the same errors
+/// as [`props_si`] otherwise. A non-finite result stops the batch with an error whose context
+/// names the index of the offending pair.
+pub fn props_si_array(
+    output: &str,
+    name1: &str,
+    prop1: &[f64],
+    name2: &str,
+    prop2: &[f64],
+    fluid: &str,
+) -> Result<Vec<f64>> {
+    if prop1.len() != prop2.len() {
+        return Err(Error::InvalidInput(format!(
+            "prop1 and prop2 must have the same length, got {} and {}",
+            prop1.len(),
+            prop2.len()
+        )));
+    }
+    let output_c = CString::new(output).map_err(|source| Error::EmbeddedNul {
+        label: "output",
+        source,
+    })?;
+    let name1_c = CString::new(name1).map_err(|source| Error::EmbeddedNul {
+        label: "name1",
+        source,
+    })?;
+    let name2_c = CString::new(name2).map_err(|source| Error::EmbeddedNul {
+        label: "name2",
+        source,
+    })?;
+    let fluid_c = CString::new(fluid).map_err(|source| Error::EmbeddedNul {
+        label: "fluid",
+        source,
+    })?;
+    prop1
+        .iter()
+        .zip(prop2)
+        .enumerate()
+        .map(|(index, (&v1, &v2))| {
+            let value = unsafe {
+                ffi::PropsSI(
+                    output_c.as_ptr(),
+                    name1_c.as_ptr(),
+                    v1,
+                    name2_c.as_ptr(),
+                    v2,
+                    fluid_c.as_ptr(),
+                )
+            };
+            if value.is_finite() {
+                return Ok(value);
+            }
+            let context =
+                format!("PropsSI({output}, {name1}={v1}, {name2}={v2}, {fluid}) at index {index}");
+            check_finite_and_report_error(value, &context)
+        })
+        .collect()
+}
+
+/// Call `PropsSI` without checking the returned value.
+fn props_si_raw(
+    output: &str,
+    name1: &str,
+    prop1: f64,
+    name2: &str,
+    prop2: f64,
+    fluid: &str,
+) -> Result<f64> {
     let output_c = CString::new(output).map_err(|source| Error::EmbeddedNul {
         label: "output",
         source,
@@ -279,7 +387,7 @@ pub fn props_si(
             fluid_c.as_ptr(),
         )
     };
-    check_finite_and_report_error(value, &context)
+    Ok(value)
 }
 
 /// Calculate a state-independent fluid property using CoolProp `Props1SI`.
@@ -299,3 +407,594 @@ pub fn props1_si(output: &str, fluid: &str) -> Result<f64> {
     let value = unsafe { ffi::Props1SI(fluid_c.as_ptr(), output_c.as_ptr()) };
     check_finite_and_report_error(value, &context)
 }
+
+/// A first partial derivative `(d of / d wrt)` at constant `at_constant`.
+///
+/// Builds the `d(A)/d(B)|C` expression accepted as a [`props_si`] output from typed
+/// [`Param`]s, so the tokens are always spelled the way CoolProp expects.
+///
+/// # Examples
+///
+/// ```rust
+/// use coolprop::{Derivative, Param};
+///
+/// let cp = Derivative {
+///     of: Param::Hmass,
+///     wrt: Param::T,
+///     at_constant: Param::P,
+/// };
+/// assert_eq!(cp.to_coolprop_expr(), "d(Hmass)/d(T)|P");
+/// ```
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub struct Derivative {
+    /// Property being differentiated.
+    pub of: Param,
+    /// Property the derivative is taken with respect to.
+    pub wrt: Param,
+    /// Property held constant.
+    pub at_constant: Param,
+}
+
+impl Derivative {
+    /// The derivative as a CoolProp output string, `d(of)/d(wrt)|at_constant`.
+    pub fn to_coolprop_expr(&self) -> String {
+        format!(
+            "d({})/d({})|{}",
+            self.of.as_coolprop_str(),
+            self.wrt.as_coolprop_str(),
+            self.at_constant.as_coolprop_str()
+        )
+    }
+}
+
+/// Evaluate a typed [`Derivative`] with [`props_si`].
+///
+/// # Errors
+///
+/// Same as [`props_si`].
+pub fn props_si_derivative(
+    deriv: &Derivative,
+    name1: &str,
+    prop1: f64,
+    name2: &str,
+    prop2: f64,
+    fluid: &str,
+) -> Result<f64> {
+    props_si(&deriv.to_coolprop_expr(), name1, prop1, name2, prop2, fluid)
+}
+
+/// Triple-point temperature (K) and pressure (Pa) of a pure fluid, as `(T_triple, p_triple)`.
+///
+/// Both values come from [`props1_si`]. Incompressible fluids (`INCOMP::` prefix) have no triple
+/// point and are rejected up front; other fluids whose model lacks one, such as some predefined
+/// mixtures, report the CoolProp failure under a `triple_point` context.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] for incompressible fluids, and [`Error::Computation`] if
+/// CoolProp cannot provide either value.
+pub fn triple_point(fluid: &str) -> Result<(f64, f64)> {
+    if fluid.starts_with("INCOMP::") {
+        return Err(Error::InvalidInput(format!(
+            "incompressible fluid {fluid} has no triple point"
+        )));
+    }
+    let lookup = |output: &str| {
+        props1_si(output, fluid).map_err(|err| Error::Computation {
+            context: format!("triple_point({fluid})"),
+            message: format!("no {output} available: {err}"),
+        })
+    };
+    Ok((lookup("T_triple")?, lookup("p_triple")?))
+}
+
+/// Sensible heat per unit mass for heating or cooling a single-phase fluid from `t1` to `t2` (K)
+/// at constant pressure `p` (Pa), in J/kg.
+///
+/// Returns `Hmass(p, t2) - Hmass(p, t1)`, the integral of `c_p dT` along the isobar; it is
+/// negative for cooling. The path must not cross the saturation dome, where the enthalpy change
+/// would include latent heat: the bubble and dew temperatures at `p` (`Q = 0` and `Q = 1`, equal
+/// for a pure fluid) are looked up and a path that passes between them is rejected. If no
+/// saturation state exists at `p`, for example above the critical pressure or for
+/// incompressible fluids, the path is accepted.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if the path crosses the saturation dome, and propagates
+/// errors from [`props_si`] for either end state.
+pub fn sensible_heat(fluid: &str, p: f64, t1: f64, t2: f64) -> Result<f64> {
+    let (t_lo, t_hi) = (t1.min(t2), t1.max(t2));
+    if let (Ok(t_bubble), Ok(t_dew)) = (
+        props_si("T", "P", p, "Q", 0.0, fluid),
+        props_si("T", "P", p, "Q", 1.0, fluid),
+    ) {
+        let (sat_lo, sat_hi) = (t_bubble.min(t_dew), t_bubble.max(t_dew));
+        if t_lo < sat_hi && t_hi > sat_lo {
+            return Err(Error::InvalidInput(format!(
+                "path from {t1} K to {t2} K at {p} Pa crosses the saturation dome of {fluid} \
+                 ({sat_lo} K to {sat_hi} K)"
+            )));
+        }
+    }
+    Ok(props_si("Hmass", "P", p, "T", t2, fluid)? - props_si("Hmass", "P", p, "T", t1, fluid)?)
+}
+
+/// Normal boiling point of a pure fluid, the saturation temperature at one standard atmosphere
+/// (101 325 Pa), in kelvin.
+///
+/// Evaluated as `props_si("T", "P", 101325, "Q", 0, fluid)` after checking that the fluid has a
+/// liquid-vapor transition at that pressure: it must lie between the triple-point and critical
+/// pressures.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if the critical pressure is at or below one atmosphere, or if
+/// the triple-point pressure is above it (as for carbon dioxide, which sublimes at one
+/// atmosphere). Errors from [`props1_si`] and [`props_si`] are propagated.
+pub fn normal_boiling_point(fluid: &str) -> Result<f64> {
+    let p = crate::STANDARD_ATMOSPHERE;
+    let p_crit = props1_si("pcrit", fluid)?;
+    if p_crit <= p {
+        return Err(Error::InvalidInput(format!(
+            "{fluid} has no normal boiling point: its critical pressure {p_crit} Pa is not above \
+             one atmosphere"
+        )));
+    }
+    // Not every model reports a triple point; the saturation call below still catches the rest.
+    if let Ok(p_triple) = props1_si("p_triple", fluid)
+        && p_triple > p
+    {
+        return Err(Error::InvalidInput(format!(
+            "{fluid} has no normal boiling point: its triple-point pressure {p_triple} Pa is above \
+             one atmosphere"
+        )));
+    }
+    props_si("T", "P", p, "Q", 0.0, fluid)
+}
+
+/// Critical compressibility factor `Z_c = p_c / (rho_c R T_c)` of a pure fluid, dimensionless.
+///
+/// Uses the molar critical density (`rhomolar_critical`) together with the molar gas constant
+/// of the fluid's own equation of state (`gas_constant`), all from [`props1_si`], so the density
+/// basis matches `R` and the result is consistent with the model's critical point. Typical values
+/// lie between about 0.23 (water) and 0.29 (simple gases).
+///
+/// # Errors
+///
+/// Propagates errors from [`props1_si`], for example for fluids without a critical point.
+pub fn critical_compressibility(fluid: &str) -> Result<f64> {
+    let p_c = props1_si("pcrit", fluid)?;
+    let t_c = props1_si("Tcrit", fluid)?;
+    let rho_c = props1_si("rhomolar_critical", fluid)?;
+    let r = props1_si("gas_constant", fluid)?;
+    Ok(p_c / (rho_c * r * t_c))
+}
+
+/// Calculate a property of an incompressible fluid at temperature `t` (K) and pressure `p` (Pa),
+/// refusing temperatures below its freezing point.
+///
+/// `fluid` is an incompressible specification such as `"INCOMP::MEG-50%"`. The solution's freeze
+/// temperature (`T_freeze`) is looked up first, and a `t` below it is rejected with both values
+/// in the message instead of CoolProp's generic correlation-range error. Otherwise this is
+/// `props_si(output, "T", t, "P", p, fluid)`.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if `t` is below the freeze point, and otherwise propagates
+/// errors from looking up `T_freeze` or evaluating the property.
+pub fn props_si_incomp(output: &str, t: f64, p: f64, fluid: &str) -> Result<f64> {
+    let t_freeze = props1_si("T_freeze", fluid)?;
+    if t < t_freeze {
+        return Err(Error::InvalidInput(format!(
+            "temperature below freeze point: T = {t} K, T_freeze = {t_freeze} K for {fluid}"
+        )));
+    }
+    props_si(output, "T", t, "P", p, fluid)
+}
+
+/// Mass-specific enthalpy (J/kg) at saturation pressure `p` (Pa) and vapor quality `q`.
+///
+/// This is the point on a P-h diagram's two-phase dome: `q = 0` and `q = 1` trace the bubble and
+/// dew lines. Equivalent to `props_si("Hmass", "P", p, "Q", q, fluid)`, with `q` checked first.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if `q` is not in `[0, 1]`, and otherwise propagates errors
+/// from [`props_si`], for example when `p` is above the critical pressure.
+pub fn enthalpy_at_pq(fluid: &str, p: f64, q: f64) -> Result<f64> {
+    if !(0.0..=1.0).contains(&q) {
+        return Err(Error::InvalidInput(format!(
+            "quality must be in [0, 1], got {q}"
+        )));
+    }
+    props_si("Hmass", "P", p, "Q", q, fluid)
+}
+
+/// Calculate a property like [`props_si`], scaling the output to kSI units.
+///
+/// Inputs are still given in SI units; only the returned value is rescaled. Exactly the outputs
+/// listed below are divided by 1000:
+///
+/// | Outputs | SI unit | kSI unit |
+/// |---------|---------|----------|
+/// | `P`, `pcrit`, `p_critical`, `ptriple`, `p_triple`, `P_min`, `P_max`, `p_reducing` | Pa | kPa |
+/// | `H`, `Hmass`, `U`, `Umass`, `G`, `Gmass`, `Helmholtzmass` | J/kg | kJ/kg |
+/// | `Hmolar`, `Umolar`, `Gmolar`, `Helmholtzmolar` | J/mol | kJ/mol |
+/// | `S`, `Smass`, `C`, `Cpmass`, `O`, `Cvmass`, `Cp0mass` | J/(kg·K) | kJ/(kg·K) |
+/// | `Smolar`, `Cpmolar`, `Cvmolar`, `Cp0molar` | J/(mol·K) | kJ/(mol·K) |
+///
+/// Any other name or alias CoolProp accepts for these outputs, such as `Pcrit`, is scaled the
+/// same way. Every other output is returned in SI units, even where its unit contains pascals
+/// or joules: the residual, ideal-gas, and excess properties such as `Hmolar_residual`,
+/// `surface_tension`, `isothermal_compressibility`, and derivative expressions such as
+/// `d(Hmass)/d(T)|P`.
+///
+/// # Errors
+///
+/// Same as [`props_si`].
+pub fn props_ksi(
+    output: &str,
+    name1: &str,
+    prop1: f64,
+    name2: &str,
+    prop2: f64,
+    fluid: &str,
+) -> Result<f64> {
+    let value = props_si(output, name1, prop1, name2, prop2, fluid)?;
+    Ok(value * ksi_output_scale(output))
+}
+
+fn ksi_output_scale(output: &str) -> f64 {
+    // Aliases such as "Pcrit" are mapped to CoolProp's canonical name before matching; names
+    // CoolProp does not know, such as derivative expressions, are matched as given.
+    let canonical = CString::new(output)
+        .ok()
+        .filter(|name| unsafe { ffi::get_param_index(name.as_ptr()) } >= 0)
+        .and_then(|name| crate::indices::parameter_information(&name, "short", "props_ksi").ok());
+    match canonical.as_deref().unwrap_or(output) {
+        "P" | "pcrit" | "p_critical" | "ptriple" | "p_triple" | "P_min" | "P_max"
+        | "p_reducing" | "H" | "Hmass" | "U" | "Umass" | "G" | "Gmass" | "Helmholtzmass"
+        | "Hmolar" | "Umolar" | "Gmolar" | "Helmholtzmolar" | "S" | "Smass" | "C" | "Cpmass"
+        | "O" | "Cvmass" | "Cp0mass" | "Smolar" | "Cpmolar" | "Cvmolar" | "Cp0molar" => 1e-3,
+        _ => 1.0,
+    }
+}
+
+/// Calculate several outputs over a series of input states in a single `PropsSImulti` call.
+///
+/// `prop1` and `prop2` hold the values of `name1` and `name2` for each input state and must have
+/// equal lengths. The result is indexed as `result[output][state]`: one row per entry of
+/// `outputs`, holding that output for every input state in the order of `prop1`/`prop2`. This
+/// avoids the per-call fluid lookup of looping over [`props_si`], which makes it much faster for
+/// grids of states.
+///
+/// `fluid` accepts the same syntax as [`props_si`]: an optional `BACKEND::` prefix (defaulting
+/// to `HEOS`) and `&`-separated components with optional `[fraction]` suffixes, parsed with
+/// [`parse_mixture_spec`]. A single bracketed value, as in `"INCOMP::MEG[0.5]"`, is passed
+/// through as the concentration of an incompressible mixture.
+///
+/// One difference: a mixture given without fractions, such as `"Methane&Ethane"`, is silently
+/// treated as equimolar here, whereas [`props_si`] rejects it because no composition is set.
+///
+/// Unlike [`props_si`], a state that CoolProp cannot evaluate does not fail the whole call; its
+/// outputs are returned as non-finite values.
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] if `prop1` and `prop2` differ in length, the fluid string
+/// mixes components with and without fractions, or the CoolProp build does not expose
+/// `PropsSImulti`. Returns [`Error::Computation`] if CoolProp rejects the request as a whole, for
+/// example an unknown fluid or output name.
+pub fn props_si_multi(
+    outputs: &[&str],
+    name1: &str,
+    prop1: &[f64],
+    name2: &str,
+    prop2: &[f64],
+    fluid: &str,
+) -> Result<Vec<Vec<f64>>> {
+    let (backend, names, fractions) = split_fluid_spec(fluid)?;
+    let fluids: Vec<&str> = names.split('&').collect();
+    let mixture = MixtureSpec {
+        backend,
+        fluids: &fluids,
+        fractions: &fractions,
+    };
+    props_si_multi_by_output(outputs, name1, prop1, name2, prop2, mixture)
+}
+
+/// Backend, component names, and mole fractions of a fluid, given separately instead of as a
+/// single `props_si`-style fluid string.
+///
+/// `fluids` and `fractions` must have the same, non-zero length; a pure fluid is
+/// `fluids: &["Water"]` with `fractions: &[1.0]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct MixtureSpec<'a> {
+    /// CoolProp backend, such as `"HEOS"`.
+    pub backend: &'a str,
+    /// Component names, in the order of `fractions`.
+    pub fluids: &'a [&'a str],
+    /// Mole fraction of each component.
+    pub fractions: &'a [f64],
+}
+
+/// [`props_si_multi`] with the fluid given as a [`MixtureSpec`] instead of a fluid string.
+///
+/// This avoids formatting and re-parsing a `"A[0.3]&B[0.7]"` string when the composition is
+/// already held as numbers. The result is indexed `result[output][state]`, as for
+/// [`props_si_multi`].
+///
+/// # Errors
+///
+/// Same as [`props_si_multi`], plus [`Error::InvalidInput`] if `mixture.fluids` is empty or its
+/// length differs from that of `mixture.fractions`.
+pub fn props_si_multi_components(
+    outputs: &[&str],
+    name1: &str,
+    prop1: &[f64],
+    name2: &str,
+    prop2: &[f64],
+    mixture: MixtureSpec<'_>,
+) -> Result<Vec<Vec<f64>>> {
+    props_si_multi_by_output(outputs, name1, prop1, name2, prop2, mixture)
+}
+
+/// Shared body of [`props_si_multi`] and [`props_si_multi_components`]. `PropsSImulti` fills
+/// its result one state at a time; it is transposed here to `result[output][state]`.
+fn props_si_multi_by_output(
+    outputs: &[&str],
+    name1: &str,
+    prop1: &[f64],
+    name2: &str,
+    prop2: &[f64],
+    mixture: MixtureSpec<'_>,
+) -> Result<Vec<Vec<f64>>> {
+    let MixtureSpec {
+        backend,
+        fluids,
+        fractions,
+    } = mixture;
+    if prop1.len() != prop2.len() {
+        return Err(Error::InvalidInput(format!(
+            "prop1 and prop2 must have equal lengths, got {} and {}",
+            prop1.len(),
+            prop2.len()
+        )));
+    }
+    if fluids.is_empty() || fluids.len() != fractions.len() {
+        return Err(Error::InvalidInput(format!(
+            "expected one mole fraction per fluid, got {} fluids and {} fractions",
+            fluids.len(),
+            fractions.len()
+        )));
+    }
+    if prop1.is_empty() || outputs.is_empty() {
+        return Ok(vec![Vec::new(); outputs.len()]);
+    }
+    let names = fluids.join("&");
+    let context = format!(
+        "PropsSImulti({}, {name1}, {name2}, {backend}::{names})",
+        outputs.join("&")
+    );
+    let outputs_c = CString::new(outputs.join("&")).map_err(|source| Error::EmbeddedNul {
+        label: "outputs",
+        source,
+    })?;
+    let name1_c = CString::new(name1).map_err(|source| Error::EmbeddedNul {
+        label: "name1",
+        source,
+    })?;
+    let name2_c = CString::new(name2).map_err(|source| Error::EmbeddedNul {
+        label: "name2",
+        source,
+    })?;
+    let backend_c = CString::new(backend).map_err(|source| Error::EmbeddedNul {
+        label: "backend",
+        source,
+    })?;
+    let names_c = CString::new(names).map_err(|source| Error::EmbeddedNul {
+        label: "fluid",
+        source,
+    })?;
+    #[cfg(coolprop_has_propssimulti)]
+    {
+        let mut prop1 = prop1.to_vec();
+        let mut prop2 = prop2.to_vec();
+        let mut rows = prop1.len() as std::os::raw::c_long;
+        let mut cols = outputs.len() as std::os::raw::c_long;
+        let mut result = vec![0.0; prop1.len() * outputs.len()];
+        unsafe {
+            ffi::PropsSImulti(
+                outputs_c.as_ptr(),
+                name1_c.as_ptr(),
+                prop1.as_mut_ptr(),
+                name2_c.as_ptr(),
+                prop2.as_mut_ptr(),
+                rows,
+                backend_c.as_ptr(),
+                names_c.as_ptr(),
+                fractions.as_ptr(),
+                fractions.len() as std::os::raw::c_long,
+                result.as_mut_ptr(),
+                &mut rows,
+                &mut cols,
+            );
+        }
+        let (rows, cols) = (rows.max(0) as usize, cols.max(0) as usize);
+        if rows != prop1.len() || cols != outputs.len() {
+            let message =
+                crate::global_param_string("errstring").unwrap_or_else(|_| "unknown error".into());
+            return Err(Error::Computation { context, message });
+        }
+        Ok((0..cols)
+            .map(|output| result.iter().skip(output).step_by(cols).copied().collect())
+            .collect())
+    }
+    #[cfg(not(coolprop_has_propssimulti))]
+    {
+        let _ = (
+            context, outputs_c, name1_c, name2_c, backend_c, names_c, fractions,
+        );
+        Err(Error::InvalidInput(
+            "this CoolProp build does not expose PropsSImulti".into(),
+        ))
+    }
+}
+
+/// Split a `props_si`-style fluid string into backend, `&`-joined component names, and mole
+/// fractions.
+fn split_fluid_spec(fluid: &str) -> Result<(&str, String, Vec<f64>)> {
+    let (backend, components) = fluid.split_once("::").unwrap_or(("HEOS", fluid));
+    let (names, fractions) = parse_mixture_spec(components)?;
+    let fractions = fractions.unwrap_or_else(|| vec![1.0 / names.len() as f64; names.len()]);
+    Ok((backend, names.join("&"), fractions))
+}
+
+/// Largest deviation from one tolerated in the sum of bracketed mole fractions.
+const FRACTION_SUM_TOLERANCE: f64 = 1e-6;
+
+/// Parse a mixture specification such as `"Methane[0.9]&Ethane[0.1]"` into component names and
+/// mole fractions.
+///
+/// Components are separated by `&`; a single name such as `"Water"` is a one-component spec.
+/// Fractions are optional, but if any component has a `[x]` suffix all of them must, and the
+/// fractions must be finite and non-negative. For specs with more than one component they must
+/// also sum to one within `1e-6`; a single component keeps its value as given, so that
+/// concentration specs such as `"MEG[0.5]"` for incompressible mixtures pass through. Without
+/// fractions the second element is `None`. Backend prefixes such as `HEOS::` are not part of the
+/// spec and must be stripped by the caller.
+///
+/// # Examples
+///
+/// ```rust
+/// use coolprop::parse_mixture_spec;
+///
+/// # fn main() -> coolprop::Result<()> {
+/// let (names, fractions) = parse_mixture_spec("Nitrogen[0.79]&Oxygen[0.21]")?;
+/// assert_eq!(names, ["Nitrogen", "Oxygen"]);
+/// assert_eq!(fractions, Some(vec![0.79, 0.21]));
+/// assert!(parse_mixture_spec("Nitrogen[0.3]&Oxygen[0.3]").is_err());
+/// assert_eq!(parse_mixture_spec("MEG[0.5]")?.1, Some(vec![0.5]));
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Returns [`Error::InvalidInput`] for empty component names, unparsable or unclosed brackets,
+/// fractions on only some components, fractions that are negative, and fractions of a
+/// multi-component spec that do not sum to one.
+pub fn parse_mixture_spec(spec: &str) -> Result<(Vec<String>, Option<Vec<f64>>)> {
+    let mut names = Vec::new();
+    let mut fractions = Vec::new();
+    for component in spec.split('&') {
+        let name = match component.split_once('[') {
+            Some((name, rest)) => {
+                let fraction = rest
+                    .strip_suffix(']')
+                    .and_then(|value| value.trim().parse::<f64>().ok())
+                    .filter(|fraction| fraction.is_finite() && *fraction >= 0.0)
+                    .ok_or_else(|| {
+                        Error::InvalidInput(format!(
+                            "invalid mole fraction in component `{component}` of `{spec}`"
+                        ))
+                    })?;
+                fractions.push(fraction);
+                name
+            }
+            None => component,
+        };
+        if name.trim().is_empty() {
+            return Err(Error::InvalidInput(format!(
+                "empty component name in `{spec}`"
+            )));
+        }
+        names.push(name.to_owned());
+    }
+    if fractions.is_empty() {
+        return Ok((names, None));
+    }
+    if fractions.len() != names.len() {
+        return Err(Error::InvalidInput(format!(
+            "either all or none of the components in `{spec}` must have mole fractions"
+        )));
+    }
+    let sum: f64 = fractions.iter().sum();
+    if names.len() > 1 && (sum - 1.0).abs() > FRACTION_SUM_TOLERANCE {
+        return Err(Error::InvalidInput(format!(
+            "mole fractions in `{spec}` sum to {sum}, expected 1"
+        )));
+    }
+    Ok((names, Some(fractions)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Derivative, parse_mixture_spec, split_fluid_spec};
+    use crate::{Error, Param};
+
+    #[test]
+    fn derivative_expressions_use_coolprop_tokens() {
+        let deriv = Derivative {
+            of: Param::P,
+            wrt: Param::T,
+            at_constant: Param::Dmolar,
+        };
+        assert_eq!(deriv.to_coolprop_expr(), "d(P)/d(T)|Dmolar");
+        let deriv = Derivative {
+            of: Param::HmolarResidual,
+            wrt: Param::Tau,
+            at_constant: Param::Delta,
+        };
+        assert_eq!(deriv.to_coolprop_expr(), "d(Hmolar_residual)/d(Tau)|Delta");
+    }
+
+    #[test]
+    fn fluid_spec_splitting() {
+        let (backend, names, fractions) = split_fluid_spec("Water").unwrap();
+        assert_eq!((backend, names.as_str()), ("HEOS", "Water"));
+        assert_eq!(fractions, vec![1.0]);
+
+        let (backend, names, fractions) =
+            split_fluid_spec("REFPROP::Nitrogen[0.79]&Oxygen[0.21]").unwrap();
+        assert_eq!((backend, names.as_str()), ("REFPROP", "Nitrogen&Oxygen"));
+        assert_eq!(fractions, vec![0.79, 0.21]);
+
+        let (_, names, fractions) = split_fluid_spec("Methane&Ethane").unwrap();
+        assert_eq!(names, "Methane&Ethane");
+        assert_eq!(fractions, vec![0.5, 0.5]);
+
+        let (backend, names, fractions) = split_fluid_spec("INCOMP::MEG[0.5]").unwrap();
+        assert_eq!((backend, names.as_str()), ("INCOMP", "MEG"));
+        assert_eq!(fractions, vec![0.5]);
+
+        assert!(split_fluid_spec("Methane[0.9]&Ethane").is_err());
+        assert!(split_fluid_spec("Methane[abc]&Ethane[0.1]").is_err());
+    }
+
+    #[test]
+    fn mixture_spec_validation() {
+        let (names, fractions) = parse_mixture_spec("R32[0.5]&R125[0.5]").unwrap();
+        assert_eq!(names, ["R32", "R125"]);
+        assert_eq!(fractions, Some(vec![0.5, 0.5]));
+        assert_eq!(
+            parse_mixture_spec("R32&R125").unwrap(),
+            (vec!["R32".to_owned(), "R125".to_owned()], None)
+        );
+
+        for spec in [
+            "A[0.3]&B[0.3]",
+            "A[0.5]&B",
+            "A[1.2]&B[-0.2]",
+            "A[0.5&B[0.5]",
+            "A[0.5]&&B[0.5]",
+            "",
+            "A[NaN]",
+        ] {
+            assert!(
+                matches!(parse_mixture_spec(spec), Err(Error::InvalidInput(_))),
+                "spec {spec:?} should be rejected"
+            );
+        }
+    }
+}
@@ -0,0 +1,275 @@
+use crate::{AbstractState, Error, InputPair, Param, Result, abstract_state::state_for_fluid};
+
+/// Saturation line of a pure fluid, sampled between its lower temperature limit and the
+/// critical point.
+///
+/// Points are stored in strictly increasing temperature order and, because saturation pressure
+/// rises with temperature, strictly increasing pressure order as well. Samples that would break
+/// either ordering (typically solver noise close to the critical point) are dropped while the
+/// curve is built, so the arrays can be handed directly to plotting or interpolation routines.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaturationCurve {
+    temperature: Vec<f64>,
+    pressure: Vec<f64>,
+    rhomolar_liq: Vec<f64>,
+    rhomolar_vap: Vec<f64>,
+}
+
+impl SaturationCurve {
+    /// Trace the saturation line of the fluid loaded in `state` with `points` evenly spaced
+    /// temperature samples.
+    ///
+    /// Sampling runs from the larger of the triple-point and minimum temperatures up to the
+    /// critical temperature. Samples at which CoolProp fails to converge, or which are not
+    /// strictly monotonic in both temperature and pressure, are skipped, so the returned curve may
+    /// hold fewer than `points` entries. The state is left at the last successfully traced point.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if `points < 2`, propagates errors from reading the
+    /// fluid's temperature limits, and returns [`Error::Computation`] if fewer than two usable
+    /// points remain.
+    pub fn build(state: &mut AbstractState, points: usize) -> Result<Self> {
+        if points < 2 {
+            return Err(Error::InvalidInput(format!(
+                "a saturation curve needs at least 2 points, got {points}"
+            )));
+        }
+        let t_low = state.get(Param::TTriple)?.max(state.get(Param::TMin)?);
+        let t_crit = state.get(Param::TCritical)?;
+        let step = (t_crit - t_low) / (points - 1) as f64;
+
+        let samples = (0..points)
+            .filter_map(|i| {
+                let t = t_low + step * i as f64;
+                trace_point(state, t).ok()
+            })
+            .collect();
+        let curve = Self::from_samples(samples);
+        if curve.temperature.len() < 2 {
+            return Err(Error::Computation {
+                context: "SaturationCurve::build".into(),
+                message: format!(
+                    "only {} usable saturation point(s) between {t_low} K and {t_crit} K",
+                    curve.temperature.len()
+                ),
+            });
+        }
+        Ok(curve)
+    }
+
+    /// Saturation temperatures, in kelvin, strictly increasing.
+    pub fn temperatures(&self) -> &[f64] {
+        &self.temperature
+    }
+
+    /// Saturation pressures, in pascals, strictly increasing and aligned with
+    /// [`temperatures`](Self::temperatures).
+    pub fn pressures_ascending(&self) -> &[f64] {
+        &self.pressure
+    }
+
+    /// Saturated-liquid molar densities, in mol/m^3.
+    pub fn rhomolar_liq(&self) -> &[f64] {
+        &self.rhomolar_liq
+    }
+
+    /// Saturated-vapor molar densities, in mol/m^3.
+    pub fn rhomolar_vap(&self) -> &[f64] {
+        &self.rhomolar_vap
+    }
+
+    /// Sort `(T, p, rhoL, rhoV)` samples by temperature and keep only those that strictly
+    /// increase both temperature and pressure over the previously kept sample.
+    fn from_samples(mut samples: Vec<[f64; 4]>) -> Self {
+        samples.retain(|sample| sample.iter().all(|v| v.is_finite()));
+        samples.sort_by(|a, b| a[0].total_cmp(&b[0]));
+
+        let mut curve = Self {
+            temperature: Vec::with_capacity(samples.len()),
+            pressure: Vec::with_capacity(samples.len()),
+            rhomolar_liq: Vec::with_capacity(samples.len()),
+            rhomolar_vap: Vec::with_capacity(samples.len()),
+        };
+        for [t, p, rho_liq, rho_vap] in samples {
+            let monotonic = match (curve.temperature.last(), curve.pressure.last()) {
+                (Some(&t_prev), Some(&p_prev)) => t > t_prev && p > p_prev,
+                _ => true,
+            };
+            if monotonic {
+                curve.temperature.push(t);
+                curve.pressure.push(p);
+                curve.rhomolar_liq.push(rho_liq);
+                curve.rhomolar_vap.push(rho_vap);
+            }
+        }
+        curve
+    }
+}
+
+/// One row of a [`SaturationTable`]: saturated-liquid and -vapor properties at a temperature.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SatRow {
+    /// Saturation temperature, in kelvin.
+    pub temperature: f64,
+    /// Saturation pressure, in pascals.
+    pub pressure: f64,
+    /// Saturated-liquid mass enthalpy, in J/kg.
+    pub h_liq: f64,
+    /// Saturated-vapor mass enthalpy, in J/kg.
+    pub h_vap: f64,
+    /// Saturated-liquid mass entropy, in J/(kg*K).
+    pub s_liq: f64,
+    /// Saturated-vapor mass entropy, in J/(kg*K).
+    pub s_vap: f64,
+}
+
+impl SatRow {
+    fn lerp(&self, other: &Self, w: f64) -> Self {
+        let mix = |a: f64, b: f64| a + w * (b - a);
+        Self {
+            temperature: mix(self.temperature, other.temperature),
+            pressure: mix(self.pressure, other.pressure),
+            h_liq: mix(self.h_liq, other.h_liq),
+            h_vap: mix(self.h_vap, other.h_vap),
+            s_liq: mix(self.s_liq, other.s_liq),
+            s_vap: mix(self.s_vap, other.s_vap),
+        }
+    }
+}
+
+/// Precomputed saturation properties of a pure fluid for cheap lookups.
+///
+/// The table is filled once by [`SaturationTable::build`] and then answers
+/// [`interpolate_at_temperature`](Self::interpolate_at_temperature) queries by linear
+/// interpolation without calling CoolProp, which keeps it off hot paths such as control loops.
+/// Accuracy depends on the row spacing: the linear interpolation error grows with the curvature
+/// of the saturation line, notably in pressure and close to the critical point.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SaturationTable {
+    rows: Vec<SatRow>,
+}
+
+impl SaturationTable {
+    /// Tabulate `n` evenly spaced saturation temperatures from `t_min` to `t_max` (K) inclusive.
+    ///
+    /// `fluid` accepts an optional `BACKEND::` prefix and defaults to `HEOS`. Every row is
+    /// evaluated with CoolProp, so `t_max` should stay below the critical temperature.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error::InvalidInput`] if `n < 2` or the temperature range is not finite and
+    /// increasing, and propagates CoolProp errors from constructing the state or evaluating any
+    /// row.
+    pub fn build(fluid: &str, t_min: f64, t_max: f64, n: usize) -> Result<Self> {
+        if n < 2 {
+            return Err(Error::InvalidInput(format!(
+                "a saturation table needs at least 2 rows, got {n}"
+            )));
+        }
+        if !(t_min.is_finite() && t_max.is_finite() && t_min < t_max) {
+            return Err(Error::InvalidInput(format!(
+                "invalid saturation table range [{t_min}, {t_max}] K"
+            )));
+        }
+        let mut state = state_for_fluid(fluid)?;
+        let step = (t_max - t_min) / (n - 1) as f64;
+        let rows = (0..n)
+            .map(|i| {
+                // Pin the last row to `t_max` to avoid accumulated rounding.
+                let t = if i == n - 1 {
+                    t_max
+                } else {
+                    t_min + step * i as f64
+                };
+                state.update(InputPair::QT, 1.0, t)?;
+                let (h_vap, s_vap) = (state.get(Param::Hmass)?, state.get(Param::Smass)?);
+                state.update(InputPair::QT, 0.0, t)?;
+                Ok(SatRow {
+                    temperature: t,
+                    pressure: state.get(Param::P)?,
+                    h_liq: state.get(Param::Hmass)?,
+                    h_vap,
+                    s_liq: state.get(Param::Smass)?,
+                    s_vap,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { rows })
+    }
+
+    /// Tabulated rows in increasing temperature order.
+    pub fn rows(&self) -> &[SatRow] {
+        &self.rows
+    }
+
+    /// Linearly interpolate every column at temperature `t` (K).
+    ///
+    /// Returns `None` if `t` is NaN or outside the tabulated range.
+    pub fn interpolate_at_temperature(&self, t: f64) -> Option<SatRow> {
+        let (first, last) = (self.rows.first()?, self.rows.last()?);
+        if !(first.temperature..=last.temperature).contains(&t) {
+            return None;
+        }
+        let upper = self
+            .rows
+            .partition_point(|row| row.temperature <= t)
+            .clamp(1, self.rows.len() - 1);
+        let (a, b) = (&self.rows[upper - 1], &self.rows[upper]);
+        let w = (t - a.temperature) / (b.temperature - a.temperature);
+        Some(a.lerp(b, w))
+    }
+}
+
+fn trace_point(state: &mut AbstractState, t: f64) -> Result<[f64; 4]> {
+    state.update(InputPair::QT, 1.0, t)?;
+    let rho_vap = state.get(Param::Dmolar)?;
+    state.update(InputPair::QT, 0.0, t)?;
+    Ok([t, state.get(Param::P)?, state.get(Param::Dmolar)?, rho_vap])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{SatRow, SaturationCurve, SaturationTable};
+
+    #[test]
+    fn samples_are_sorted_and_non_monotonic_points_dropped() {
+        let curve = SaturationCurve::from_samples(vec![
+            [300.0, 3.0, 10.0, 1.0],
+            [250.0, 2.0, 11.0, 0.5],
+            [400.0, 5.0, 8.0, 2.0],
+            // Duplicate temperature.
+            [300.0, 3.5, 10.0, 1.0],
+            // Pressure dips near the end of the curve.
+            [450.0, 4.5, 7.0, 3.0],
+            [f64::NAN, 6.0, 6.0, 4.0],
+            [500.0, 6.0, 6.0, 4.0],
+        ]);
+        assert_eq!(curve.temperatures(), &[250.0, 300.0, 400.0, 500.0]);
+        assert_eq!(curve.pressures_ascending(), &[2.0, 3.0, 5.0, 6.0]);
+        assert_eq!(curve.rhomolar_liq(), &[11.0, 10.0, 8.0, 6.0]);
+        assert_eq!(curve.rhomolar_vap(), &[0.5, 1.0, 2.0, 4.0]);
+    }
+
+    #[test]
+    fn table_interpolates_linearly_within_range() {
+        let row = |t: f64| SatRow {
+            temperature: t,
+            pressure: 10.0 * t,
+            h_liq: t,
+            h_vap: 2.0 * t,
+            s_liq: -t,
+            s_vap: 0.5 * t,
+        };
+        let table = SaturationTable {
+            rows: vec![row(300.0), row(310.0), row(330.0)],
+        };
+        assert_eq!(table.interpolate_at_temperature(300.0), Some(row(300.0)));
+        assert_eq!(table.interpolate_at_temperature(305.0), Some(row(305.0)));
+        assert_eq!(table.interpolate_at_temperature(325.0), Some(row(325.0)));
+        assert_eq!(table.interpolate_at_temperature(330.0), Some(row(330.0)));
+        assert_eq!(table.interpolate_at_temperature(299.9), None);
+        assert_eq!(table.interpolate_at_temperature(330.1), None);
+        assert_eq!(table.interpolate_at_temperature(f64::NAN), None);
+    }
+}
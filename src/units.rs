@@ -0,0 +1,98 @@
+//! Interop with the [`uom`] units-of-measure crate.
+//!
+//! Enabled by the `uom` feature. CoolProp works exclusively in base SI units, which is also how
+//! `uom` stores every quantity internally, so conversion at the boundary is exact and free: a
+//! `uom` quantity is read as its base-unit value and results are wrapped without scaling.
+
+use std::marker::PhantomData;
+
+use uom::si::{Dimension, Quantity, SI};
+
+use crate::{Result, props_si};
+
+/// A value that can cross the CoolProp boundary as a plain SI number.
+///
+/// Implemented for every `uom` SI quantity with `f64` storage (for example
+/// `ThermodynamicTemperature`, `Pressure`, `MassDensity`, `AvailableEnergy`, `Ratio`) and for
+/// bare `f64`, which is taken to already be in SI units.
+pub trait SiQuantity: Sized {
+    /// The value in base SI units.
+    fn to_si(&self) -> f64;
+
+    /// Wrap a value given in base SI units.
+    fn from_si(value: f64) -> Self;
+}
+
+impl<D> SiQuantity for Quantity<D, SI<f64>, f64>
+where
+    D: Dimension + ?Sized,
+{
+    fn to_si(&self) -> f64 {
+        self.value
+    }
+
+    fn from_si(value: f64) -> Self {
+        Quantity {
+            dimension: PhantomData,
+            units: PhantomData,
+            value,
+        }
+    }
+}
+
+impl SiQuantity for f64 {
+    fn to_si(&self) -> f64 {
+        *self
+    }
+
+    fn from_si(value: f64) -> Self {
+        value
+    }
+}
+
+/// [`props_si`] with `uom` quantities as inputs and output.
+///
+/// The output type is chosen by the caller and must match the dimension of `output`: CoolProp
+/// only reports a number, so asking for `"T"` as a `Pressure` is not detected. Input names are
+/// likewise not checked against the quantities passed for them.
+///
+/// # Examples
+///
+/// ```rust
+/// use coolprop::props_si_uom;
+/// use uom::si::f64::{Pressure, Ratio, ThermodynamicTemperature};
+/// use uom::si::{pressure::bar, ratio::ratio, thermodynamic_temperature::degree_celsius};
+///
+/// # fn main() -> coolprop::Result<()> {
+/// # if cfg!(cp_docs_rs) { return Ok(()); }
+/// let t: ThermodynamicTemperature = props_si_uom(
+///     "T",
+///     "P",
+///     Pressure::new::<bar>(1.01325),
+///     "Q",
+///     Ratio::new::<ratio>(0.0),
+///     "Water",
+/// )?;
+/// assert!((t.get::<degree_celsius>() - 100.0).abs() < 0.01);
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+///
+/// Same as [`props_si`].
+pub fn props_si_uom<Q, A, B>(
+    output: &str,
+    name1: &str,
+    prop1: A,
+    name2: &str,
+    prop2: B,
+    fluid: &str,
+) -> Result<Q>
+where
+    Q: SiQuantity,
+    A: SiQuantity,
+    B: SiQuantity,
+{
+    props_si(output, name1, prop1.to_si(), name2, prop2.to_si(), fluid).map(Q::from_si)
+}
@@ -3,8 +3,13 @@ mod common;
 
 use anyhow::Result;
 use common::{assert_close, test_lock};
-use coolprop::{AbstractState, InputPair, Param, Phase, props_si};
+use coolprop::{
+    AbstractState, ComponentIndex, Error, InputPair, Param, Phase, PropertyStream, critical_locus,
+    enthalpy_difference, entropy_generation, fluid_constants_table,
+    phase_envelopes_over_compositions, phase_grid, property_grid, props_si, props1_si,
+};
 use static_assertions::{assert_impl_all, assert_not_impl_any};
+use std::collections::HashMap;
 
 assert_impl_all!(AbstractState: Send);
 assert_not_impl_any!(AbstractState: Sync);
@@ -24,6 +29,8 @@ fn basic_state_metadata() -> Result<()> {
     assert_eq!(backend, "HelmholtzEOSBackend");
     let fluids = state.fluid_names()?;
     assert_eq!(fluids, "R134a");
+    assert_eq!(state.backend_name_strict()?, backend);
+    assert_eq!(state.fluid_names_strict()?, fluids);
 
     state.update(InputPair::PT, 1.0e5, 300.0)?;
 
@@ -42,6 +49,42 @@ fn basic_state_metadata() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn supercritical_predicate() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+
+    state.update(InputPair::PT, 30.0e6, 700.0)?;
+    assert!(state.is_supercritical()?, "water at 30 MPa and 700 K");
+
+    state.update(InputPair::PT, 101_325.0, 300.0)?;
+    assert!(!state.is_supercritical()?, "water at 1 atm and 300 K");
+
+    Ok(())
+}
+
+#[test]
+fn two_phase_predicate() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+
+    let rho_liq = props_si("Dmass", "P", 101_325.0, "Q", 0.0, "Water")?;
+    let rho_vap = props_si("Dmass", "P", 101_325.0, "Q", 1.0, "Water")?;
+    let rho_mid = 1.0 / (0.5 / rho_liq + 0.5 / rho_vap);
+    state.update(InputPair::DmassP, rho_mid, 101_325.0)?;
+    assert!(state.is_two_phase()?, "water at 1 atm inside the dome");
+    assert_close(state.get(Param::Q)?, 0.5, 1e-6, 0.0, "quality");
+
+    state.update(InputPair::PT, 101_325.0, 300.0)?;
+    assert!(!state.is_two_phase()?, "water at 1 atm and 300 K");
+    state.update(InputPair::PT, 101_325.0, 400.0)?;
+    assert!(!state.is_two_phase()?, "water at 1 atm and 400 K");
+    state.update(InputPair::PT, 30.0e6, 700.0)?;
+    assert!(!state.is_two_phase()?, "water at 30 MPa and 700 K");
+
+    Ok(())
+}
+
 #[test]
 fn debug_includes_runtime_metadata() -> Result<()> {
     let _guard = test_lock().lock().unwrap();
@@ -62,6 +105,28 @@ fn debug_includes_runtime_metadata() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn update_inputs_appear_in_debug_and_errors() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    state.update(InputPair::PT, 101_325.0, 300.0)?;
+    let dbg = format!("{state:?}");
+    assert!(
+        dbg.contains("update(PT_INPUTS, v1=101325 Pa, v2=300 K)"),
+        "Debug output should include the last update: {dbg}"
+    );
+
+    let err = state
+        .update(InputPair::PT, -1.0, 300.0)
+        .expect_err("negative pressure should be rejected");
+    assert!(
+        matches!(&err, Error::CoolProp { message, .. }
+            if message.starts_with("update(PT_INPUTS, v1=-1 Pa, v2=300 K) failed: ")),
+        "unexpected update error: {err}"
+    );
+    Ok(())
+}
+
 #[test]
 fn try_clone_reconstructs_state() -> Result<()> {
     let _guard = test_lock().lock().unwrap();
@@ -120,6 +185,16 @@ fn update_and_retrieve_properties() -> Result<()> {
         "pressure from dmolar/t update shortcut",
     );
 
+    let dmass = props_si("Dmass", "P", pressure, "T", temperature, "R134a")?;
+    state.update_dmass_t(dmass, temperature)?;
+    assert_close(
+        state.pressure()?,
+        pressure,
+        1e-9,
+        1e-3,
+        "pressure from dmass/t update shortcut",
+    );
+
     state.update(InputPair::DmolarT, dmolar, temperature)?;
     let pressure_roundtrip = state.get(Param::P)?;
     assert_close(
@@ -133,6 +208,35 @@ fn update_and_retrieve_properties() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn get_checked_matches_get_for_finite_values() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R134a")?;
+    state.update(InputPair::PT, 1.0e5, 300.0)?;
+    let raw = state.get(Param::Hmass)?;
+    let checked = state.get_checked(Param::Hmass)?;
+    assert_eq!(raw, checked);
+    Ok(())
+}
+
+#[test]
+fn state_dependent_get_requires_update() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R134a")?;
+    let err = state
+        .get(Param::Hmass)
+        .expect_err("enthalpy needs a thermodynamic state");
+    assert!(
+        matches!(&err, Error::InvalidInput(msg) if msg == "state not initialized; call update first"),
+        "unexpected error: {err}"
+    );
+    assert!(state.get(Param::TCritical)? > 0.0);
+
+    state.update(InputPair::PT, 1.0e5, 300.0)?;
+    assert!(state.get(Param::Hmass)?.is_finite());
+    Ok(())
+}
+
 #[test]
 fn saturation_queries() -> Result<()> {
     let _guard = test_lock().lock().unwrap();
@@ -232,6 +336,19 @@ fn derivative_queries() -> Result<()> {
         }
     }
 
+    state.update(InputPair::PT, 8.0e5, 320.0)?;
+    let err = state
+        .first_two_phase_deriv(Param::Hmolar, Param::T, Param::Q)
+        .expect_err("single-phase state should be rejected");
+    assert!(
+        matches!(&err, Error::InvalidInput(msg) if msg == "two-phase derivative requires a two-phase state"),
+        "unexpected error: {err}"
+    );
+    assert!(matches!(
+        state.first_two_phase_deriv_splined(Param::Hmolar, Param::T, Param::Q, 0.1),
+        Err(Error::InvalidInput(_))
+    ));
+
     Ok(())
 }
 
@@ -285,6 +402,150 @@ fn fractions_and_fugacity() -> Result<()> {
         "component fugacity coefficient should be finite"
     );
 
+    assert_eq!(state.component_count()?, 2);
+    let first = ComponentIndex::new(&state, 0)?;
+    assert_eq!(state.get_fugacity(first)?, f0);
+    assert_eq!(state.get_fugacity_coefficient(first)?, phi0);
+    assert!(ComponentIndex::new(&state, 1).is_ok());
+    assert!(matches!(
+        ComponentIndex::new(&state, 2),
+        Err(Error::InvalidInput(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn feed_composition_differs_from_phase_compositions() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R32&R134a")?;
+    let feed = [0.4, 0.6];
+    state.set_fractions(&feed)?;
+    state.update(InputPair::QT, 0.5, 260.0)?;
+
+    let current = state.feed_mole_fractions()?;
+    assert_eq!(current, state.mole_fractions()?);
+    for (idx, &value) in current.iter().enumerate() {
+        assert_close(value, feed[idx], 1e-9, 1e-12, "two-phase feed fraction");
+    }
+
+    let liquid = state.mole_fractions_sat_state(Phase::Liquid)?;
+    let vapor = state.mole_fractions_sat_state(Phase::Gas)?;
+    assert!(
+        (liquid[0] - vapor[0]).abs() > 1e-3,
+        "phase compositions should differ: liquid={liquid:?}, vapor={vapor:?}"
+    );
+    assert!(
+        vapor[0] > feed[0] && liquid[0] < feed[0],
+        "the more volatile R32 should enrich the vapor: liquid={liquid:?}, vapor={vapor:?}"
+    );
+
+    assert_eq!(state.mole_fractions_for_phase_code(0)?, liquid);
+    assert_eq!(state.mole_fractions_for_phase_code(5)?, vapor);
+    assert!(matches!(
+        state.mole_fractions_for_phase_code(6),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        state.mole_fractions_for_phase_code(42),
+        Err(Error::UnknownPhaseCode(42))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn k_values_divide_vapor_by_liquid_fractions() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R32&R134a")?;
+    state.set_fractions(&[0.4, 0.6])?;
+    state.update(InputPair::QT, 0.5, 260.0)?;
+    let k = state.k_values()?;
+    let liquid = state.mole_fractions_sat_state(Phase::Liquid)?;
+    let vapor = state.mole_fractions_sat_state(Phase::Gas)?;
+    assert_eq!(k.len(), 2);
+    for i in 0..2 {
+        assert_close(k[i], vapor[i] / liquid[i], 1e-12, 0.0, "K-value");
+    }
+    assert!(k[0] > 1.0 && k[1] < 1.0, "R32 is the more volatile: {k:?}");
+
+    state.update(InputPair::PT, 1.0e5, 300.0)?;
+    let err = state
+        .k_values()
+        .expect_err("single-phase state should be rejected");
+    assert!(
+        matches!(&err, Error::InvalidInput(msg) if msg == "K-values requires a two-phase state"),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn excess_enthalpy_of_pure_and_ideal_mixtures() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut pure = AbstractState::new("HEOS", "Water")?;
+    pure.update(InputPair::PT, 101_325.0, 300.0)?;
+    assert_close(pure.excess_enthalpy()?, 0.0, 0.0, 1e-9, "pure-fluid excess");
+
+    let mut air = AbstractState::new("HEOS", "Nitrogen&Oxygen")?;
+    air.set_fractions(&[0.79, 0.21])?;
+    air.update(InputPair::PT, 101_325.0, 300.0)?;
+    let h_excess = air.excess_enthalpy()?;
+    assert!(
+        h_excess.abs() < 5.0,
+        "near-ideal gas mixture should have a small excess enthalpy: {h_excess}"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn empty_batches_skip_coolprop() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R134a")?;
+    let outputs = state.update_and_common_out(InputPair::PT, &[], &[])?;
+    assert!(outputs.temperature.is_empty() && outputs.smolar.is_empty());
+    assert!(
+        state
+            .update_and_1_out(InputPair::PT, &[], &[], Param::T)?
+            .is_empty()
+    );
+    let outs = state.update_and_5_out(InputPair::PT, &[], &[], [Param::T; 5])?;
+    assert!(outs.iter().all(Vec::is_empty));
+    // Nothing was evaluated, so the state is still uninitialized.
+    assert!(matches!(state.get(Param::T), Err(Error::InvalidInput(_))));
+    Ok(())
+}
+
+#[test]
+fn property_stream_matches_batch_output() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R134a")?;
+    let pressures = [1.0e5, 2.0e5, 3.0e5];
+    let temperatures = [280.0, 300.0, 320.0];
+    let batch = state.update_and_1_out(InputPair::PT, &pressures, &temperatures, Param::Dmass)?;
+
+    let inputs = pressures.into_iter().zip(temperatures);
+    let stream = PropertyStream::new(&mut state, InputPair::PT, Param::Dmass, inputs);
+    assert_eq!(stream.size_hint(), (3, Some(3)));
+    let streamed = stream.collect::<coolprop::Result<Vec<_>>>()?;
+    for (s, b) in streamed.iter().zip(&batch) {
+        assert_close(*s, *b, 1e-12, 1e-9, "streamed density");
+    }
+
+    // A bad point fails on its own without ending the stream.
+    let inputs = [(1.0e5, 300.0), (-1.0e5, 300.0), (2.0e5, 300.0)];
+    let results: Vec<_> =
+        PropertyStream::new(&mut state, InputPair::PT, Param::T, inputs).collect();
+    assert_eq!(results.len(), 3);
+    assert!(results[1].is_err());
+    assert_close(
+        *results[2].as_ref().unwrap(),
+        300.0,
+        1e-12,
+        1e-9,
+        "stream after error",
+    );
     Ok(())
 }
 
@@ -469,6 +730,11 @@ fn cubic_parameter_mutators() -> Result<()> {
     state.set_fractions(&[0.5, 0.5])?;
 
     state.set_binary_interaction_double(0, 1, "kij", 0.05)?;
+    assert_eq!(state.get_binary_interaction_double(0, 1, "kij")?, 0.05);
+    assert!(matches!(
+        state.get_binary_interaction_double(0, 1, "not_a_parameter"),
+        Err(Error::CoolProp { .. })
+    ));
     state.set_cubic_alpha_c(0, "MC", 1.0, 0.5, 0.25)?;
     state.set_cubic_alpha_c(1, "MC", 0.9, 0.4, 0.2)?;
     state.set_fluid_parameter_double(0, "cm", 0.0)?;
@@ -480,3 +746,813 @@ fn cubic_parameter_mutators() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn phase_envelopes_preserve_composition_order() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let compositions = vec![vec![0.3, 0.7], vec![0.7, 0.3]];
+    let envelopes = phase_envelopes_over_compositions("HEOS", "R32&R125", &compositions)?;
+    assert_eq!(envelopes.len(), compositions.len());
+
+    let mut state = AbstractState::new("HEOS", "R32&R125")?;
+    for (fractions, envelope) in compositions.iter().zip(&envelopes) {
+        state.set_fractions(fractions)?;
+        state.build_phase_envelope("none")?;
+        let expected = state.phase_envelope()?;
+        assert_eq!(envelope.temperature.len(), expected.temperature.len());
+        assert_close(
+            envelope.temperature[0],
+            expected.temperature[0],
+            1e-9,
+            1e-9,
+            "envelope order matches composition order",
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn fluid_constants_table_matches_props1_si() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let fluids = ["Water", "R134a", "INCOMP::MEG-50%"];
+    let outputs = [Param::TCritical, Param::MolarMass];
+    let table = fluid_constants_table(&fluids, &outputs)?;
+    assert_eq!(table.len(), fluids.len());
+    assert!(table.iter().all(|row| row.len() == outputs.len()));
+
+    let tcrit_water = table[0][0].expect("water has a critical temperature");
+    assert_close(
+        tcrit_water,
+        props1_si("Tcrit", "Water")?,
+        1e-12,
+        1e-9,
+        "water critical temperature",
+    );
+    assert!(table[1][1].is_some(), "R134a has a molar mass");
+    assert!(
+        table[2][0].is_none(),
+        "incompressible solutions have no critical temperature"
+    );
+
+    assert!(fluid_constants_table(&["NotAFluid"], &outputs).is_err());
+    // State-dependent outputs are an error, not a missing constant.
+    assert!(matches!(
+        fluid_constants_table(&["Water"], &[Param::Hmass]),
+        Err(Error::InvalidInput(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn joule_thomson_inversion_temperature() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Nitrogen")?;
+    state.update(InputPair::PT, 101_325.0, 300.0)?;
+    let mu = state.joule_thomson()?;
+    assert!(
+        mu > 1e-6 && mu < 5e-6,
+        "nitrogen should cool on expansion at ambient conditions: {mu}"
+    );
+
+    let t_inv = state.inversion_temperature(101_325.0)?;
+    assert!(
+        (550.0..700.0).contains(&t_inv),
+        "unexpected nitrogen inversion temperature: {t_inv}"
+    );
+    assert!(
+        state.joule_thomson()?.abs() < 1e-10,
+        "Joule-Thomson coefficient should vanish at the inversion temperature"
+    );
+    assert!(state.inversion_temperature(-1.0).is_err());
+    Ok(())
+}
+
+#[test]
+fn helmholtz_derivatives_are_consistent() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Nitrogen")?;
+    let (rho, t) = (2_000.0, 300.0);
+    state.update(InputPair::DmolarT, rho, t)?;
+    let derivs = state.helmholtz_derivatives()?;
+
+    let r = state.get(Param::GasConstant)?;
+    let p_from_alphar = rho * r * t * (1.0 + derivs.delta * derivs.dalphar_ddelta);
+    assert_close(p_from_alphar, state.pressure()?, 1e-10, 1e-6, "pressure");
+
+    // Central differences of the first derivatives reproduce the derived second derivatives.
+    let h = 1e-4;
+    state.update(InputPair::DmolarT, rho * (1.0 + h), t)?;
+    let plus = state.helmholtz_derivatives()?;
+    state.update(InputPair::DmolarT, rho * (1.0 - h), t)?;
+    let minus = state.helmholtz_derivatives()?;
+    let d_delta = plus.delta - minus.delta;
+    assert_close(
+        (plus.dalphar_ddelta - minus.dalphar_ddelta) / d_delta,
+        derivs.d2alphar_ddelta2,
+        1e-5,
+        1e-12,
+        "d2alphar_ddelta2",
+    );
+
+    state.update(InputPair::DmolarT, rho, t / (1.0 + h))?;
+    let plus = state.helmholtz_derivatives()?;
+    state.update(InputPair::DmolarT, rho, t / (1.0 - h))?;
+    let minus = state.helmholtz_derivatives()?;
+    let d_tau = plus.tau - minus.tau;
+    assert_close(
+        (plus.dalphar_dtau - minus.dalphar_dtau) / d_tau,
+        derivs.d2alphar_dtau2,
+        1e-5,
+        1e-12,
+        "d2alphar_dtau2",
+    );
+    assert_close(
+        (plus.dalpha0_dtau - minus.dalpha0_dtau) / d_tau,
+        derivs.d2alpha0_dtau2,
+        1e-5,
+        1e-12,
+        "d2alpha0_dtau2",
+    );
+    assert_close(
+        (plus.dalphar_ddelta - minus.dalphar_ddelta) / d_tau,
+        derivs.d2alphar_ddelta_dtau,
+        1e-5,
+        1e-12,
+        "d2alphar_ddelta_dtau",
+    );
+
+    Ok(())
+}
+
+#[test]
+fn normalized_fractions_sum_to_one() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R32&R125")?;
+    state.set_fractions_normalized(&[0.3, 0.3])?;
+    let fractions = state.mole_fractions()?;
+    assert_eq!(fractions.len(), 2);
+    for value in fractions {
+        assert_close(value, 0.5, 1e-12, 1e-12, "normalized mole fraction");
+    }
+    assert!(state.set_fractions_normalized(&[0.0, 0.0]).is_err());
+    Ok(())
+}
+
+#[test]
+fn named_fractions_are_ordered_by_component() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R32&R125")?;
+    assert_eq!(state.fluid_names_vec()?, vec!["R32", "R125"]);
+
+    state.set_fractions_named(&HashMap::from([("R125", 0.6), ("R32", 0.4)]))?;
+    let fractions = state.mole_fractions()?;
+    assert_close(fractions[0], 0.4, 1e-12, 1e-12, "R32 mole fraction");
+    assert_close(fractions[1], 0.6, 1e-12, 1e-12, "R125 mole fraction");
+
+    assert!(
+        state
+            .set_fractions_named(&HashMap::from([("R32", 0.4), ("Water", 0.6)]))
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn enthalpy_difference_matches_props_si() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let inlet = (InputPair::PT, 101_325.0, 300.0);
+    let outlet = (InputPair::PQ, 101_325.0, 1.0);
+    let dh = enthalpy_difference("Water", inlet, outlet)?;
+    let expected = props_si("Hmass", "P", 101_325.0, "Q", 1.0, "Water")?
+        - props_si("Hmass", "P", 101_325.0, "T", 300.0, "Water")?;
+    assert_close(dh, expected, 1e-9, 1e-6, "water heating and boiling duty");
+    assert_close(
+        enthalpy_difference("Water", outlet, inlet)?,
+        -dh,
+        1e-12,
+        1e-9,
+        "reversed enthalpy difference",
+    );
+    Ok(())
+}
+
+#[test]
+fn entropy_generation_of_heating_and_throttling() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let inlet = (InputPair::PT, 101_325.0, 300.0);
+    let outlet = (InputPair::PQ, 101_325.0, 1.0);
+    let q_in = enthalpy_difference("Water", inlet, outlet)?;
+    let ds = props_si("Smass", "P", 101_325.0, "Q", 1.0, "Water")?
+        - props_si("Smass", "P", 101_325.0, "T", 300.0, "Water")?;
+    let s_gen = entropy_generation("Water", inlet, outlet, 500.0, q_in)?;
+    assert_close(s_gen, ds - q_in / 500.0, 1e-9, 1e-9, "heating from 500 K");
+    assert!(s_gen > 0.0);
+
+    // Adiabatic throttling of a gas only generates entropy.
+    let h = props_si("Hmass", "P", 2.0e6, "T", 300.0, "Nitrogen")?;
+    let s_gen = entropy_generation(
+        "Nitrogen",
+        (InputPair::PT, 2.0e6, 300.0),
+        (InputPair::HmassP, h, 1.0e5),
+        300.0,
+        0.0,
+    )?;
+    assert!(s_gen > 0.0, "throttling s_gen = {s_gen}");
+
+    assert!(matches!(
+        entropy_generation("Water", inlet, outlet, 0.0, q_in),
+        Err(Error::InvalidInput(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn named_partial_derivatives_match_props_si() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    let (p, t) = (101_325.0, 300.0);
+    state.update(InputPair::PT, p, t)?;
+
+    let cases = [
+        (
+            state.density_temperature_derivative_isobaric()?,
+            "d(Dmass)/d(T)|P",
+        ),
+        (
+            state.density_pressure_derivative_isothermal()?,
+            "d(Dmass)/d(P)|T",
+        ),
+        (
+            state.enthalpy_pressure_derivative_isothermal()?,
+            "d(Hmass)/d(P)|T",
+        ),
+        (
+            state.pressure_temperature_derivative_isochoric()?,
+            "d(P)/d(T)|Dmass",
+        ),
+    ];
+    for (value, output) in cases {
+        let expected = props_si(output, "P", p, "T", t, "Water")?;
+        assert_close(value, expected, 1e-9, 1e-15, output);
+    }
+    Ok(())
+}
+
+#[test]
+fn critical_locus_spans_pure_fluid_critical_points() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    assert!(matches!(
+        critical_locus("HEOS", ("R32", "R125"), 0),
+        Err(Error::InvalidInput(_))
+    ));
+
+    let locus = critical_locus("HEOS", ("R32", "R125"), 5)?;
+    assert!(!locus.is_empty(), "critical locus should contain points");
+    assert!(locus.windows(2).all(|w| w[0].0 < w[1].0));
+    let t_r32 = props1_si("Tcrit", "R32")?;
+    let t_r125 = props1_si("Tcrit", "R125")?;
+    for (x, point) in &locus {
+        assert!(*x > 0.0 && *x < 1.0, "composition {x} should be interior");
+        assert!(point.stable);
+        assert!(
+            (t_r125 - 5.0..=t_r32 + 5.0).contains(&point.temperature),
+            "critical temperature {} at x = {x} outside the pure-fluid range",
+            point.temperature
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn phase_envelope_slice_matches_saturation_flash() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R32&R125")?;
+    state.set_fractions(&[0.5, 0.5])?;
+    state.build_phase_envelope("none")?;
+    let envelope = state.phase_envelope()?;
+
+    let p = 1.0e6;
+    let (t_bubble, t_dew) = envelope
+        .at_pressure(p)
+        .expect("1 MPa should lie inside the R32/R125 envelope");
+    state.update(InputPair::PQ, p, 0.0)?;
+    assert_close(
+        t_bubble,
+        state.get(Param::T)?,
+        0.0,
+        0.5,
+        "bubble temperature",
+    );
+    state.update(InputPair::PQ, p, 1.0)?;
+    assert_close(t_dew, state.get(Param::T)?, 0.0, 0.5, "dew temperature");
+    assert!(t_bubble <= t_dew + 1e-6);
+
+    let p_max = envelope.pressure.iter().copied().fold(f64::MIN, f64::max);
+    assert_eq!(envelope.at_pressure(2.0 * p_max), None);
+    Ok(())
+}
+
+#[test]
+fn property_grid_marks_failed_points_as_nan() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let t = [300.0, 350.0, 400.0];
+    let p = [1.0e5, 1.0e6, -1.0];
+    let grid = property_grid("Water", Param::Dmass, &t, &p)?;
+    assert_eq!(grid.len(), t.len());
+    for (row, &ti) in grid.iter().zip(&t) {
+        assert_eq!(row.len(), p.len());
+        for (&value, &pj) in row.iter().zip(&p[..2]) {
+            let expected = props_si("Dmass", "T", ti, "P", pj, "Water")?;
+            assert_close(value, expected, 1e-12, 0.0, "grid density");
+        }
+        assert!(row[2].is_nan(), "negative pressure should yield NaN");
+    }
+    assert!(property_grid("NotAFluid", Param::Dmass, &t, &p).is_err());
+    Ok(())
+}
+
+#[test]
+fn phase_grid_marks_failed_points_as_unknown() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let t = [300.0, 500.0, 700.0];
+    let p = [1.0e5, 30.0e6, -1.0];
+    let grid = phase_grid("Water", &t, &p)?;
+    assert_eq!(grid.len(), t.len());
+    assert!(grid.iter().all(|row| row.len() == p.len()));
+    assert_eq!(grid[0][0], Phase::Liquid);
+    assert_eq!(grid[1][0], Phase::Gas);
+    assert_eq!(grid[2][1], Phase::Supercritical);
+    assert!(grid.iter().all(|row| row[2] == Phase::Unknown));
+    assert!(phase_grid("NotAFluid", &t, &p).is_err());
+    Ok(())
+}
+
+#[test]
+fn clear_returns_to_an_unupdated_state() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R32&R125")?;
+    state.set_fractions(&[0.4, 0.6])?;
+    state.update(InputPair::PT, 3.0e5, 290.0)?;
+    state.specify_phase(Phase::Gas)?;
+    let old_handle = state.handle();
+
+    state.clear()?;
+    assert_ne!(state.handle(), old_handle);
+    assert!(matches!(
+        state.get(Param::Hmass),
+        Err(Error::InvalidInput(_))
+    ));
+    assert_eq!(state.mole_fractions()?, vec![0.4, 0.6]);
+    assert!(!format!("{state:?}").contains("PT_INPUTS"));
+
+    state.update(InputPair::PT, 3.0e5, 290.0)?;
+    assert!(state.get(Param::Hmass)?.is_finite());
+    Ok(())
+}
+
+#[test]
+fn latent_heat_scales_with_remaining_liquid() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R134a")?;
+    state.update(InputPair::QT, 0.3, 260.0)?;
+    let h_fg = props_si("Hmass", "T", 260.0, "Q", 1.0, "R134a")?
+        - props_si("Hmass", "T", 260.0, "Q", 0.0, "R134a")?;
+
+    assert_close(
+        state.latent_heat_at_quality(0.0)?,
+        h_fg,
+        1e-9,
+        1e-6,
+        "h_fg at q = 0",
+    );
+    assert_close(
+        state.latent_heat_at_quality(0.3)?,
+        0.7 * h_fg,
+        1e-9,
+        1e-6,
+        "remaining latent heat at q = 0.3",
+    );
+    assert_eq!(state.latent_heat_at_quality(1.0)?, 0.0);
+    for q in [-0.1, 1.1, f64::NAN] {
+        assert!(matches!(
+            state.latent_heat_at_quality(q),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn enthalpy_split_sums_to_total() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "CarbonDioxide")?;
+    for (p, t) in [(1.0e5, 300.0), (10.0e6, 310.0), (6.0e6, 280.0)] {
+        state.update(InputPair::PT, p, t)?;
+        let (ideal, residual) = state.enthalpy_split()?;
+        assert_close(
+            ideal + residual,
+            state.get(Param::Hmolar)?,
+            1e-9,
+            1e-6,
+            "molar enthalpy split",
+        );
+        let (ideal, residual) = state.enthalpy_split_mass()?;
+        assert_close(
+            ideal + residual,
+            state.get(Param::Hmass)?,
+            1e-9,
+            1e-6,
+            "mass enthalpy split",
+        );
+    }
+    // Dilute gas: the residual part is small compared with R*T.
+    state.update(InputPair::PT, 1.0e5, 300.0)?;
+    let (_, residual) = state.enthalpy_split()?;
+    assert!(
+        residual.abs() < 0.05 * 8.314 * 300.0,
+        "residual {residual} J/mol"
+    );
+    Ok(())
+}
+
+#[test]
+fn saturated_phases_have_equal_gibbs_energy() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R134a")?;
+    for t in [220.0, 260.0, 340.0] {
+        state.update(InputPair::QT, 0.5, t)?;
+        let residual = state.saturation_gibbs_equality_residual()?;
+        let scale = state.saturated_liquid_keyed_output(Param::Gmass)?.abs();
+        assert!(
+            residual.abs() <= 1e-8 * scale.max(1e3),
+            "Gibbs residual {residual} J/kg at {t} K"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn mach_number_uses_real_gas_speed_of_sound() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Air")?;
+    state.update(InputPair::PT, 101_325.0, 288.15)?;
+    let a = props_si("speed_of_sound", "P", 101_325.0, "T", 288.15, "Air")?;
+    assert_close(
+        state.mach_number(a)?,
+        1.0,
+        1e-12,
+        0.0,
+        "Mach at the speed of sound",
+    );
+    assert_close(
+        state.mach_number(170.0)?,
+        170.0 / a,
+        1e-12,
+        0.0,
+        "subsonic Mach",
+    );
+    Ok(())
+}
+
+#[test]
+fn property_at_isentropic_keeps_binary_interaction_parameters() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("PR", "Methane&Ethane")?;
+    state.set_fractions(&[0.7, 0.3])?;
+    state.set_binary_interaction_double(0, 1, "kij", 0.1)?;
+    state.update(InputPair::PT, 5.0e6, 300.0)?;
+    let s = state.get(Param::Smolar)?;
+    let t2 = state.property_at_isentropic(Param::T, 1.0e6)?;
+
+    // The same flash on the tuned state itself.
+    state.update(InputPair::PSmolar, 1.0e6, s)?;
+    assert_close(t2, state.get(Param::T)?, 1e-9, 1e-9, "tuned isentrope");
+    Ok(())
+}
+
+#[test]
+fn property_at_isentropic_leaves_state_untouched() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Nitrogen")?;
+    state.update(InputPair::PT, 1.0e6, 300.0)?;
+    let s = state.get(Param::Smass)?;
+    let t2 = state.property_at_isentropic(Param::T, 1.0e5)?;
+    let expected = props_si("T", "P", 1.0e5, "S", s, "Nitrogen")?;
+    assert_close(t2, expected, 1e-8, 1e-8, "isentropic outlet temperature");
+    assert!(t2 < 300.0, "expansion should cool the gas, got {t2} K");
+    assert_close(
+        state.property_at_isentropic(Param::Smass, 1.0e5)?,
+        s,
+        1e-9,
+        1e-9,
+        "entropy along the isentrope",
+    );
+    assert_close(state.get(Param::P)?, 1.0e6, 1e-12, 0.0, "original pressure");
+    assert_close(
+        state.get(Param::T)?,
+        300.0,
+        1e-12,
+        0.0,
+        "original temperature",
+    );
+    Ok(())
+}
+
+#[test]
+fn gruneisen_parameter_matches_heat_capacity_form() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Nitrogen")?;
+    state.update(InputPair::PT, 1.0e3, 300.0)?;
+    let gamma = state.get(Param::Cpmass)? / state.get(Param::Cvmass)?;
+    assert_close(
+        state.gruneisen_parameter()?,
+        gamma - 1.0,
+        1e-3,
+        0.0,
+        "dilute gas",
+    );
+
+    state.update(InputPair::PT, 20.0e6, 150.0)?;
+    let expected = state.pressure_temperature_derivative_isochoric()?
+        / (state.get(Param::Dmass)? * state.get(Param::Cvmass)?);
+    assert_close(
+        state.gruneisen_parameter()?,
+        expected,
+        1e-8,
+        0.0,
+        "dense fluid",
+    );
+    Ok(())
+}
+
+#[test]
+fn saturated_heat_capacity_matches_entropy_slope() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let t = 373.15;
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    state.update(InputPair::QT, 0.3, t)?;
+    let c_liq = state.saturated_heat_capacity(Phase::Liquid)?;
+    let c_vap = state.saturated_heat_capacity(Phase::Gas)?;
+    assert!(
+        c_vap < 0.0,
+        "saturated water vapor has c_sigma < 0, got {c_vap}"
+    );
+
+    let mut branch = AbstractState::new("HEOS", "Water")?;
+    for (q, c_sigma) in [(0.0, c_liq), (1.0, c_vap)] {
+        branch.update(InputPair::QT, q, t)?;
+        let expected = t * branch.first_saturation_deriv(Param::Smass, Param::T)?;
+        assert_close(c_sigma, expected, 1e-6, 1e-6, "T (ds/dT)_sat");
+    }
+    assert_close(state.get(Param::Q)?, 0.3, 1e-12, 0.0, "original quality");
+    assert!(matches!(
+        state.saturated_heat_capacity(Phase::TwoPhase),
+        Err(Error::InvalidInput(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn bulk_modulus_is_reciprocal_compressibility() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    state.update(InputPair::PT, 101_325.0, 293.15)?;
+    let kappa = state.isothermal_compressibility()?;
+    assert_eq!(kappa, state.get(Param::IsothermalCompressibility)?);
+    let bulk = state.bulk_modulus()?;
+    assert_close(bulk * kappa, 1.0, 1e-12, 0.0, "K_T * kappa_T");
+    assert!(
+        (2.0e9..2.4e9).contains(&bulk),
+        "liquid water bulk modulus should be about 2.2 GPa, got {bulk} Pa"
+    );
+    Ok(())
+}
+
+#[test]
+fn polytropic_exponent_of_ideal_and_real_gases() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Nitrogen")?;
+    state.update(InputPair::PT, 1.0e3, 300.0)?;
+    let gamma = state.get(Param::Cpmass)? / state.get(Param::Cvmass)?;
+    assert_close(state.polytropic_exponent()?, gamma, 1e-4, 0.0, "dilute gas");
+
+    state.update(InputPair::PT, 10.0e6, 250.0)?;
+    let a = state.get(Param::SpeedOfSound)?;
+    let expected = state.get(Param::Dmass)? * a * a / state.get(Param::P)?;
+    assert_close(
+        state.polytropic_exponent()?,
+        expected,
+        1e-8,
+        0.0,
+        "rho a^2 / P",
+    );
+    Ok(())
+}
+
+#[test]
+fn quality_sweep_crosses_the_dome_along_an_isobar() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R134a")?;
+    let p = 500_000.0;
+    let qualities = [0.0, 0.25, 0.5, 0.75, 1.0];
+    let h = state.quality_sweep(p, &qualities, Param::Hmass)?;
+    assert_eq!(h.len(), qualities.len());
+    for (&value, &q) in h.iter().zip(&qualities) {
+        let expected = props_si("Hmass", "P", p, "Q", q, "R134a")?;
+        assert_close(value, expected, 1e-9, 1e-6, "isobar enthalpy");
+    }
+    assert!(h.windows(2).all(|pair| pair[1] > pair[0]));
+    assert_close(state.get(Param::Q)?, 1.0, 1e-12, 0.0, "last quality");
+    assert!(state.quality_sweep(p, &[0.5, 1.5], Param::Hmass).is_err());
+    Ok(())
+}
+
+#[test]
+fn specific_exergy_relative_to_dead_state() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let (t0, p0) = (298.15, 101_325.0);
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    state.update(InputPair::PT, 2.0e6, 600.0)?;
+    let h0 = props_si("Hmass", "P", p0, "T", t0, "Water")?;
+    let s0 = props_si("Smass", "P", p0, "T", t0, "Water")?;
+    let expected = (state.get(Param::Hmass)? - h0) - t0 * (state.get(Param::Smass)? - s0);
+    let psi = state.specific_exergy(t0, p0)?;
+    assert_close(psi, expected, 1e-9, 1e-6, "superheated steam exergy");
+    assert!(psi > 0.0);
+    assert_close(state.get(Param::T)?, 600.0, 1e-12, 0.0, "state untouched");
+
+    state.update(InputPair::PT, p0, t0)?;
+    assert!(state.specific_exergy(t0, p0)?.abs() < 1e-6);
+    Ok(())
+}
+
+#[test]
+fn specific_exergy_keeps_binary_interaction_parameters() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let (t0, p0) = (298.15, 101_325.0);
+    let mut state = AbstractState::new("PR", "Methane&Ethane")?;
+    state.set_fractions(&[0.7, 0.3])?;
+    state.set_binary_interaction_double(0, 1, "kij", 0.1)?;
+    state.update(InputPair::PT, 5.0e6, 350.0)?;
+    let (h, s) = (state.get(Param::Hmass)?, state.get(Param::Smass)?);
+    let psi = state.specific_exergy(t0, p0)?;
+
+    // The dead state evaluated on the tuned state itself.
+    state.update(InputPair::PT, p0, t0)?;
+    let expected = (h - state.get(Param::Hmass)?) - t0 * (s - state.get(Param::Smass)?);
+    assert_close(psi, expected, 1e-9, 1e-6, "tuned mixture exergy");
+    Ok(())
+}
+
+#[test]
+fn update_with_stats_reports_phase() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    let stats = state.update_with_stats(InputPair::PT, 101_325.0, 300.0)?;
+    assert_eq!(stats.phase, Phase::Liquid);
+    assert_close(state.get(Param::T)?, 300.0, 1e-12, 0.0, "temperature");
+
+    let stats = state.update_with_stats(InputPair::PQ, 101_325.0, 0.5)?;
+    assert_eq!(stats.phase, Phase::TwoPhase);
+    assert!(stats.elapsed < std::time::Duration::from_secs(10));
+
+    assert!(matches!(
+        state.update_with_stats(InputPair::PT, -1.0, 300.0),
+        Err(Error::CoolProp { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn update_from_params_orders_values_for_coolprop() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut expected = AbstractState::new("HEOS", "Water")?;
+    expected.update(InputPair::PT, 1e5, 300.0)?;
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    for (a, va, b, vb) in [
+        (Param::T, 300.0, Param::P, 1e5),
+        (Param::P, 1e5, Param::T, 300.0),
+    ] {
+        state.update_from_params(a, va, b, vb)?;
+        assert_eq!(state.get(Param::Hmass)?, expected.get(Param::Hmass)?);
+    }
+
+    let rho = expected.get(Param::Dmolar)?;
+    state.update_from_params(Param::T, 300.0, Param::Dmolar, rho)?;
+    assert_close(
+        state.get(Param::P)?,
+        1e5,
+        1e-8,
+        0.0,
+        "pressure from (T, Dmolar)",
+    );
+
+    assert!(matches!(
+        state.update_from_params(Param::Cpmass, 4180.0, Param::Viscosity, 1e-3),
+        Err(Error::InvalidInput(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn two_phase_density_adds_specific_volumes() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    state.update(InputPair::PQ, 101_325.0, 0.3)?;
+    let rho = state.two_phase_density(0.3)?;
+    assert_close(
+        rho,
+        state.get(Param::Dmass)?,
+        1e-9,
+        0.0,
+        "homogeneous density",
+    );
+    assert_close(
+        state.two_phase_density(0.0)?,
+        state.saturated_liquid_keyed_output(Param::Dmass)?,
+        1e-12,
+        0.0,
+        "saturated liquid density",
+    );
+    assert!(state.two_phase_density(1.0)? < 1.0);
+    assert!(matches!(
+        state.two_phase_density(1.2),
+        Err(Error::InvalidInput(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn get_by_name_reads_outputs_by_coolprop_name() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    state.update(InputPair::PT, 101_325.0, 300.0)?;
+    assert_eq!(state.get_by_name("T")?, state.get(Param::T)?);
+    // Aliases outside the enum's tokens resolve too, and repeat lookups hit the cache.
+    for _ in 0..2 {
+        assert_eq!(state.get_by_name("D")?, state.get(Param::Dmass)?);
+    }
+    assert!(matches!(
+        state.get_by_name("not_a_parameter"),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        state.get_by_name("T\0"),
+        Err(Error::EmbeddedNul { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn mixture_macro_matches_explicit_setup() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut from_macro = coolprop::mixture!("HEOS", Methane => 0.9, Ethane => 0.1)?;
+    assert_eq!(from_macro.fluid_names_vec()?, ["Methane", "Ethane"]);
+    assert_eq!(from_macro.mole_fractions()?, [0.9, 0.1]);
+
+    let mut explicit = AbstractState::new("HEOS", "Methane&Ethane")?;
+    explicit.set_fractions(&[0.9, 0.1])?;
+    from_macro.update(InputPair::PT, 101_325.0, 300.0)?;
+    explicit.update(InputPair::PT, 101_325.0, 300.0)?;
+    assert_eq!(from_macro.get(Param::Dmass)?, explicit.get(Param::Dmass)?);
+
+    let pure = coolprop::mixture!("HEOS", Water => 1.0)?;
+    assert_eq!(pure.component_count()?, 1);
+    assert!(coolprop::mixture!("HEOS", NotAFluid => 0.5, Water => 0.5).is_err());
+    Ok(())
+}
+
+#[test]
+fn average_molar_mass_weights_components_by_mole_fraction() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let state = coolprop::mixture!("HEOS", Methane => 0.9, Ethane => 0.1)?;
+    let expected =
+        0.9 * props1_si("molar_mass", "Methane")? + 0.1 * props1_si("molar_mass", "Ethane")?;
+    assert_close(
+        state.average_molar_mass()?,
+        expected,
+        1e-9,
+        0.0,
+        "mixture molar mass",
+    );
+    assert_eq!(state.average_molar_mass()?, state.get(Param::MolarMass)?);
+    Ok(())
+}
+
+#[test]
+fn heat_capacities_on_mass_and_molar_basis() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Nitrogen")?;
+    state.update(InputPair::PT, 101_325.0, 300.0)?;
+    let (cp, cv) = state.heat_capacities()?;
+    assert_eq!(cp, state.get(Param::Cpmass)?);
+    assert_eq!(cv, state.get(Param::Cvmass)?);
+    assert_close(cp / cv, 1.4, 0.01, 0.0, "nitrogen heat capacity ratio");
+
+    let (cp_molar, cv_molar) = state.heat_capacities_molar()?;
+    let molar_mass = state.average_molar_mass()?;
+    assert_close(cp_molar, cp * molar_mass, 1e-9, 0.0, "molar cp");
+    assert_close(cv_molar, cv * molar_mass, 1e-9, 0.0, "molar cv");
+    Ok(())
+}
@@ -4,8 +4,10 @@ mod common;
 use anyhow::Result;
 use common::test_lock;
 use coolprop::{
-    get_config_bool, get_config_double, get_config_string, set_config_bool, set_config_double,
-    set_config_string,
+    AbstractState, ConfigGuard, Error, Param, Paths, R_U_DEFAULT, add_fluids_from_json,
+    config_as_json, fluid_param_string, get_config_bool, get_config_double, get_config_string,
+    get_debug_level, props1_si, set_config_bool, set_config_double, set_config_string,
+    set_departure_functions, universal_gas_constant,
 };
 
 #[test]
@@ -34,3 +36,145 @@ fn set_config_wrappers_allow_updates() -> Result<()> {
     assert_eq!(punctuation, ".");
     Ok(())
 }
+
+#[test]
+fn add_fluids_from_json_rejects_invalid_definitions() {
+    let _guard = test_lock().lock().unwrap();
+    assert!(add_fluids_from_json("HEOS", "definitely not JSON").is_err());
+    assert!(add_fluids_from_json("HEOS", "[\0]").is_err());
+}
+
+#[test]
+fn set_departure_functions_rejects_invalid_definitions() {
+    let _guard = test_lock().lock().unwrap();
+    assert!(matches!(
+        set_departure_functions("definitely not JSON"),
+        Err(Error::CoolProp { .. } | Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        set_departure_functions("[\0]"),
+        Err(Error::EmbeddedNul { .. })
+    ));
+}
+
+#[test]
+fn add_fluids_from_json_registers_a_custom_fluid() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    // A renamed copy of a bundled fluid stands in for an in-house EOS fit.
+    let name = "CoolPropRsTestArgon";
+    let mut fluid: serde_json::Value = serde_json::from_str(&fluid_param_string("Argon", "JSON")?)?;
+    if fluid.is_array() {
+        fluid = fluid[0].take();
+    }
+    let info = &mut fluid["INFO"];
+    info["NAME"] = name.into();
+    info["ALIASES"] = serde_json::json!([]);
+    info["CAS"] = "0-00-0".into();
+    info["REFPROP_NAME"] = "N/A".into();
+    assert!(AbstractState::new("HEOS", name).is_err());
+
+    add_fluids_from_json("HEOS", &serde_json::json!([fluid]).to_string())?;
+    let state = AbstractState::new("HEOS", name)?;
+    assert_eq!(
+        state.get(Param::MolarMass)?,
+        props1_si("molar_mass", "Argon")?
+    );
+    Ok(())
+}
+
+#[test]
+fn paths_are_validated_before_any_key_is_set() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let original = get_config_string("ALTERNATIVE_TABLES_DIRECTORY")?;
+    let missing = std::env::temp_dir().join("coolprop-rs-missing-refprop-dir");
+    let err = Paths {
+        refprop: Some(missing.clone()),
+        tables_directory: Some(std::env::temp_dir()),
+        ..Paths::default()
+    }
+    .apply()
+    .expect_err("missing REFPROP directory should be rejected");
+    assert!(
+        matches!(&err, Error::InvalidInput(msg) if msg.contains(&*missing.to_string_lossy())),
+        "unexpected error: {err}"
+    );
+    assert_eq!(get_config_string("ALTERNATIVE_TABLES_DIRECTORY")?, original);
+
+    Paths::default().apply()?;
+    let tables = std::env::temp_dir();
+    Paths {
+        tables_directory: Some(tables.clone()),
+        ..Paths::default()
+    }
+    .apply()?;
+    assert_eq!(
+        get_config_string("ALTERNATIVE_TABLES_DIRECTORY")?,
+        tables.to_string_lossy()
+    );
+    set_config_string("ALTERNATIVE_TABLES_DIRECTORY", &original)?;
+    Ok(())
+}
+
+#[test]
+fn universal_gas_constant_follows_configuration() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let original = universal_gas_constant();
+    assert!((original - R_U_DEFAULT).abs() < 1e-6, "R = {original}");
+    set_config_double("R_U_CODATA", 8.3145)?;
+    let changed = universal_gas_constant();
+    set_config_double("R_U_CODATA", original)?;
+    assert_eq!(changed, 8.3145);
+    assert_eq!(universal_gas_constant(), original);
+    Ok(())
+}
+
+#[test]
+fn config_as_json_reflects_current_settings() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let original = get_config_double("SPINODAL_MINIMUM_DELTA")?;
+    set_config_double("SPINODAL_MINIMUM_DELTA", 0.25)?;
+    let json = config_as_json();
+    set_config_double("SPINODAL_MINIMUM_DELTA", original)?;
+    let json = json?;
+    assert!(json.trim_start().starts_with('{'), "{json}");
+    assert!(json.contains("\"SPINODAL_MINIMUM_DELTA\""), "{json}");
+    assert!(json.contains("0.25"), "{json}");
+    assert!(matches!(
+        get_config_string("NOT_A_CONFIG_KEY"),
+        Err(Error::InvalidInput(_)) | Err(Error::CoolPropGlobalError { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn config_guard_restores_previous_values() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let normalize = get_config_bool("NORMALIZE_GAS_CONSTANTS")?;
+    let r_u = get_config_double("R_U_CODATA")?;
+    {
+        let _bool_guard = ConfigGuard::set_bool("NORMALIZE_GAS_CONSTANTS", !normalize)?;
+        let _double_guard = ConfigGuard::set_double("R_U_CODATA", 8.3145)?;
+        assert_eq!(get_config_bool("NORMALIZE_GAS_CONSTANTS")?, !normalize);
+        assert_eq!(get_config_double("R_U_CODATA")?, 8.3145);
+    }
+    assert_eq!(get_config_bool("NORMALIZE_GAS_CONSTANTS")?, normalize);
+    assert_eq!(get_config_double("R_U_CODATA")?, r_u);
+
+    let punctuation = get_config_string("FLOAT_PUNCTUATION")?;
+    drop(ConfigGuard::set_string("FLOAT_PUNCTUATION", ",")?);
+    assert_eq!(get_config_string("FLOAT_PUNCTUATION")?, punctuation);
+
+    assert!(ConfigGuard::set_bool("bad\0key", true).is_err());
+    Ok(())
+}
+
+#[test]
+fn config_guard_restores_debug_level() {
+    let _guard = test_lock().lock().unwrap();
+    let before = get_debug_level();
+    {
+        let _debug_guard = ConfigGuard::set_debug_level(before + 1);
+        assert_eq!(get_debug_level(), before + 1);
+    }
+    assert_eq!(get_debug_level(), before);
+}
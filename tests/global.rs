@@ -2,7 +2,11 @@
 mod common;
 
 use common::test_lock;
-use coolprop::{fluid_param_string, global_param_string, phase_si, set_reference_state};
+use coolprop::{
+    AbstractState, Error, GlobalParamReader, Param, build_info, canonical_fluid_name,
+    fluid_aliases, fluid_param_string, global_param_string, known_fluids, last_warning, phase_si,
+    props_si, props1_si, reference_offset, set_reference_state, with_reference_state,
+};
 
 #[test]
 fn global_param_string_version_nonempty() {
@@ -14,6 +18,64 @@ fn global_param_string_version_nonempty() {
     );
 }
 
+#[test]
+fn build_info_collects_global_strings() {
+    let _guard = test_lock().lock().unwrap();
+    let info = build_info().expect("build info should be available");
+    assert_eq!(info.version, global_param_string("version").unwrap());
+    assert_eq!(
+        info.git_revision,
+        global_param_string("gitrevision").unwrap()
+    );
+    assert!(!info.version.is_empty());
+    assert!(
+        info.fluid_count
+            .is_some_and(|count| count >= known_fluids().len())
+    );
+    assert!(
+        info.refprop_version
+            .as_deref()
+            .is_none_or(|version| !version.is_empty() && version != "n/a")
+    );
+}
+
+#[test]
+fn last_warning_is_cleared_once_read() {
+    let _guard = test_lock().lock().unwrap();
+    let _ = last_warning();
+    props_si("Hmass", "P", 101_325.0, "T", 300.0, "Water").unwrap();
+    let _ = last_warning();
+    assert_eq!(last_warning(), None);
+}
+
+#[test]
+fn global_param_reader_reuses_its_buffer() {
+    let _guard = test_lock().lock().unwrap();
+    let mut reader = GlobalParamReader::new();
+    let fluids = reader
+        .read("FluidsList")
+        .expect("fluid list should be available");
+    assert!(fluids.len() > 256, "fluid list should need a grown buffer");
+    assert_eq!(fluids, global_param_string("FluidsList").unwrap());
+    assert_eq!(
+        reader.read("version").unwrap(),
+        global_param_string("version").unwrap()
+    );
+    // An unknown key fails at once with CoolProp's message instead of growing the buffer.
+    let err = reader
+        .read("__definitely_not_a_valid_global_param__")
+        .expect_err("unknown key should be rejected");
+    assert!(
+        matches!(&err, Error::GlobalParameter { message, .. } if !message.contains("too small")),
+        "unexpected error: {err}"
+    );
+    assert!(reader.read("bad\0param").is_err());
+    assert_eq!(
+        reader.read("version").unwrap(),
+        global_param_string("version").unwrap()
+    );
+}
+
 #[test]
 fn global_param_string_invalid_parameter_errors() {
     let _guard = test_lock().lock().unwrap();
@@ -33,6 +95,35 @@ fn fluid_param_string_aliases_nonempty() {
     assert!(!aliases.trim().is_empty());
 }
 
+#[test]
+fn fluid_aliases_resolve_to_the_same_fluid() {
+    let _guard = test_lock().lock().unwrap();
+    let aliases = fluid_aliases("Water").expect("aliases should be available");
+    assert!(aliases.iter().any(|alias| alias == "H2O"), "{aliases:?}");
+    let t_crit = props1_si("Tcrit", "Water").unwrap();
+    for alias in &aliases {
+        assert!(!alias.contains(',') && alias.trim() == alias);
+        assert_eq!(props1_si("Tcrit", alias).unwrap(), t_crit, "alias {alias}");
+    }
+}
+
+#[test]
+fn canonical_fluid_name_resolves_aliases() {
+    let _guard = test_lock().lock().unwrap();
+    for input in ["co2", "R744", "CarbonDioxide", " carbondioxide "] {
+        assert_eq!(
+            canonical_fluid_name(input).unwrap(),
+            "CarbonDioxide",
+            "input {input:?}"
+        );
+    }
+    assert_eq!(canonical_fluid_name("H2O").unwrap(), "Water");
+    assert!(matches!(
+        canonical_fluid_name("NotAFluid"),
+        Err(Error::InvalidInput(_))
+    ));
+}
+
 #[test]
 fn phase_si_returns_phase_label() {
     let _guard = test_lock().lock().unwrap();
@@ -50,3 +141,54 @@ fn set_reference_state_accepts_default_reset() {
         .expect("setting default reference state should succeed");
     set_reference_state("Water", "DEF").expect("setting DEF reference state should succeed");
 }
+
+#[test]
+fn reference_state_is_scoped_and_offsets_are_constant() {
+    let _guard = test_lock().lock().unwrap();
+    let h_liq = || props_si("Hmass", "T", 273.15, "Q", 0.0, "R134a");
+    let h_default = h_liq().expect("default enthalpy");
+
+    // IIR fixes h = 200 kJ/kg and s = 1 kJ/(kg*K) for saturated liquid at 0 degC.
+    let (h_iir, s_iir) = with_reference_state("R134a", "IIR", || {
+        Ok((h_liq()?, props_si("Smass", "T", 273.15, "Q", 0.0, "R134a")?))
+    })
+    .expect("IIR reference state");
+    assert!((h_iir - 200e3).abs() < 1e-3, "IIR enthalpy {h_iir}");
+    assert!((s_iir - 1e3).abs() < 1e-6, "IIR entropy {s_iir}");
+    assert_eq!(h_liq().expect("restored enthalpy"), h_default);
+
+    let (dh_cold, ds_cold) =
+        reference_offset("R134a", 250.0, "ASHRAE", "IIR").expect("offset at 250 K");
+    let (dh_warm, ds_warm) =
+        reference_offset("R134a", 300.0, "ASHRAE", "IIR").expect("offset at 300 K");
+    assert!((dh_cold - dh_warm).abs() < 1e-6, "{dh_cold} vs {dh_warm}");
+    assert!((ds_cold - ds_warm).abs() < 1e-9, "{ds_cold} vs {ds_warm}");
+    assert_eq!(h_liq().expect("restored enthalpy"), h_default);
+}
+
+#[test]
+fn known_fluids_are_available() {
+    let _guard = test_lock().lock().unwrap();
+    let fluids_list = global_param_string("FluidsList").expect("FluidsList should be available");
+    let available: Vec<&str> = fluids_list.split(',').map(str::trim).collect();
+    for &fluid in known_fluids() {
+        assert!(
+            available.contains(&fluid),
+            "{fluid} is missing from FluidsList"
+        );
+        AbstractState::new("HEOS", fluid)
+            .unwrap_or_else(|err| panic!("{fluid} should construct an HEOS state: {err}"));
+    }
+}
+
+#[test]
+fn param_units_and_descriptions_come_from_coolprop() {
+    let _guard = test_lock().lock().unwrap();
+    assert_eq!(Param::P.units().expect("pressure units"), "Pa");
+    assert_eq!(Param::T.units().expect("temperature units"), "K");
+    assert_eq!(Param::Hmass.units().expect("enthalpy units"), "J/kg");
+    for param in [Param::P, Param::Hmass, Param::Viscosity] {
+        let description = param.description().expect("description");
+        assert!(!description.is_empty(), "{param:?} has no description");
+    }
+}
@@ -3,7 +3,11 @@ mod common;
 
 use anyhow::Result;
 use common::test_lock;
-use coolprop::ha_props_si;
+use coolprop::{
+    Error, HumidAirState, STANDARD_ATMOSPHERE, adiabatic_saturation_temperature,
+    dew_or_frost_point, ha_props_si, ha_props_si_atm, mix_airstreams, moist_air_density,
+    moist_air_density_dry_basis, moist_air_specific_heat, props_si, sensible_latent_split,
+};
 
 #[test]
 fn humidity_roundtrip_relative_humidity() -> Result<()> {
@@ -34,3 +38,191 @@ fn invalid_relative_humidity_range_errors() {
         "unexpected error message content: {msg}"
     );
 }
+
+#[test]
+fn standard_atmosphere_shortcut_fixes_pressure() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    for (output, name, value) in [("W", "R", 0.5), ("Twb", "R", 0.4), ("Hda", "W", 0.01)] {
+        let expected = ha_props_si(output, "T", 300.0, "P", STANDARD_ATMOSPHERE, name, value)?;
+        let actual = ha_props_si_atm(output, "T", 300.0, name, value)?;
+        assert_eq!(actual, expected, "{output} at standard atmosphere");
+    }
+    Ok(())
+}
+
+#[test]
+fn dew_point_above_freezing_and_frost_point_below() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let p = STANDARD_ATMOSPHERE;
+
+    let dew = dew_or_frost_point(298.15, p, 0.6)?;
+    let tdp = ha_props_si("Tdp", "T", 298.15, "P", p, "R", 0.6)?;
+    assert!((dew - tdp).abs() < 0.1, "dew point {dew} K vs Tdp {tdp} K");
+
+    // Below freezing the vapor pressure is under the triple-point pressure of water.
+    assert!(ha_props_si("psi_w", "T", 263.15, "P", p, "R", 0.8)? < 611.657);
+    let frost = dew_or_frost_point(263.15, p, 0.8)?;
+    assert!(
+        frost > 250.0 && frost < 263.15,
+        "frost point {frost} K should lie just below the dry bulb"
+    );
+
+    // Saturated air just above the triple point lands on the liquid branch.
+    let saturated = dew_or_frost_point(275.0, p, 1.0)?;
+    let t_sat = props_si(
+        "T",
+        "P",
+        ha_props_si("psi_w", "T", 275.0, "P", p, "R", 1.0)?,
+        "Q",
+        1.0,
+        "Water",
+    )?;
+    assert!((saturated - t_sat).abs() < 1e-9);
+
+    assert!(matches!(
+        dew_or_frost_point(298.15, p, 0.0),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        dew_or_frost_point(298.15, p, 1.5),
+        Err(Error::InvalidInput(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn humid_air_state_matches_individual_calls() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let (t, p, rh) = (303.15, STANDARD_ATMOSPHERE, 0.65);
+    let state = HumidAirState::from(t, p, rh)?;
+    assert_eq!(state.t_dry, t);
+    assert_eq!(state.pressure, p);
+    assert_eq!(state.relative_humidity, rh);
+    for (value, output) in [
+        (state.t_wet, "Twb"),
+        (state.t_dew, "Tdp"),
+        (state.humidity_ratio, "W"),
+        (state.enthalpy, "Hda"),
+        (state.specific_volume, "Vda"),
+    ] {
+        assert_eq!(
+            value,
+            ha_props_si(output, "T", t, "P", p, "R", rh)?,
+            "{output}"
+        );
+    }
+    assert!(state.t_dew < state.t_wet && state.t_wet < state.t_dry);
+    Ok(())
+}
+
+#[test]
+fn adiabatic_saturation_temperature_matches_thermodynamic_wet_bulb() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let p = STANDARD_ATMOSPHERE;
+    for (t, rh) in [(303.15, 0.3), (313.15, 0.5), (298.15, 0.9)] {
+        let w = ha_props_si("W", "T", t, "P", p, "R", rh)?;
+        let t_as = adiabatic_saturation_temperature(t, p, w)?;
+        let t_wb = ha_props_si("Twb", "T", t, "P", p, "R", rh)?;
+        assert!(
+            (t_as - t_wb).abs() < 0.05,
+            "T_as = {t_as} K vs Twb = {t_wb} K at {t} K, RH {rh}"
+        );
+        assert!(t_as < t);
+    }
+
+    let w_sat = ha_props_si("W", "T", 300.0, "P", p, "R", 1.0)?;
+    let t_as = adiabatic_saturation_temperature(300.0, p, w_sat)?;
+    assert!(
+        (t_as - 300.0).abs() < 1e-5,
+        "saturated air gives T_as = {t_as} K"
+    );
+
+    for w in [-0.001, 2.0 * w_sat] {
+        assert!(matches!(
+            adiabatic_saturation_temperature(300.0, p, w),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn moist_air_density_on_both_bases() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let (t, p, rh) = (303.15, STANDARD_ATMOSPHERE, 0.6);
+    let rho = moist_air_density(t, p, rh)?;
+    let rho_da = moist_air_density_dry_basis(t, p, rh)?;
+    assert_eq!(rho, 1.0 / ha_props_si("Vha", "T", t, "P", p, "R", rh)?);
+    let w = ha_props_si("W", "T", t, "P", p, "R", rh)?;
+    assert!(
+        (rho / rho_da - (1.0 + w)).abs() < 1e-9,
+        "rho = {rho}, rho_da = {rho_da}, W = {w}"
+    );
+    assert!((1.1..1.2).contains(&rho), "moist air density {rho} kg/m3");
+    Ok(())
+}
+
+#[test]
+fn moist_air_specific_heat_is_the_enthalpy_slope() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let (t, p, rh) = (298.15, STANDARD_ATMOSPHERE, 0.5);
+    let cp = moist_air_specific_heat(t, p, rh)?;
+    let w = ha_props_si("W", "T", t, "P", p, "R", rh)?;
+    let dt = 0.01;
+    let slope = (ha_props_si("Hda", "T", t + dt, "P", p, "W", w)?
+        - ha_props_si("Hda", "T", t - dt, "P", p, "W", w)?)
+        / (2.0 * dt);
+    assert!(
+        (cp / slope - 1.0).abs() < 1e-4,
+        "cp = {cp} vs dh/dT = {slope}"
+    );
+    assert!((1000.0..1050.0).contains(&cp), "cp = {cp} J/(kg_da K)");
+    Ok(())
+}
+
+#[test]
+fn sensible_latent_split_of_a_cooling_coil() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let p = STANDARD_ATMOSPHERE;
+    let inlet = (300.15, p, 0.5);
+    let outlet = (286.15, p, 0.9);
+    let (sensible, latent) = sensible_latent_split(inlet, outlet)?;
+    let h_in = ha_props_si("Hda", "T", inlet.0, "P", p, "R", inlet.2)?;
+    let h_out = ha_props_si("Hda", "T", outlet.0, "P", p, "R", outlet.2)?;
+    assert!(((sensible + latent) - (h_out - h_in)).abs() < 1e-6);
+    assert!(sensible < 0.0 && latent < 0.0, "{sensible}, {latent}");
+
+    // Heating without moisture change is purely sensible.
+    let w = ha_props_si("W", "T", 290.0, "P", p, "R", 0.5)?;
+    let rh_warm = ha_props_si("R", "T", 310.0, "P", p, "W", w)?;
+    let (sensible, latent) = sensible_latent_split((290.0, p, 0.5), (310.0, p, rh_warm))?;
+    assert!(latent.abs() < 1e-3, "latent = {latent}");
+    assert!(sensible > 0.0);
+    Ok(())
+}
+
+#[test]
+fn mix_airstreams_balances_water_and_energy() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let p = STANDARD_ATMOSPHERE;
+    let outdoor = (1.0, 305.15, p, 0.6);
+    let returned = (3.0, 297.15, p, 0.5);
+    let (t_mix, w_mix) = mix_airstreams(outdoor, returned)?;
+
+    let w = |(_, t, p, rh): (f64, f64, f64, f64)| ha_props_si("W", "T", t, "P", p, "R", rh);
+    let h = |(_, t, p, rh): (f64, f64, f64, f64)| ha_props_si("Hda", "T", t, "P", p, "R", rh);
+    let expected_w = (w(outdoor)? + 3.0 * w(returned)?) / 4.0;
+    assert!((w_mix - expected_w).abs() < 1e-12);
+    let expected_h = (h(outdoor)? + 3.0 * h(returned)?) / 4.0;
+    let h_mix = ha_props_si("Hda", "T", t_mix, "P", p, "W", w_mix)?;
+    assert!((h_mix - expected_h).abs() < 1e-3, "{h_mix} vs {expected_h}");
+    assert!(t_mix > returned.1 && t_mix < outdoor.1);
+
+    let (t_same, _) = mix_airstreams(returned, (0.0, 350.0, p, 0.1))?;
+    assert!((t_same - returned.1).abs() < 1e-6);
+    assert!(matches!(
+        mix_airstreams((0.0, 300.0, p, 0.5), (0.0, 300.0, p, 0.5)),
+        Err(Error::InvalidInput(_))
+    ));
+    Ok(())
+}
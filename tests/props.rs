@@ -2,7 +2,12 @@
 mod common;
 
 use anyhow::Result;
-use coolprop::{props_si, props1_si};
+use coolprop::{
+    Derivative, Error, MixtureSpec, Param, critical_compressibility, enthalpy_at_pq,
+    normal_boiling_point, props_ksi, props_si, props_si_array, props_si_derivative,
+    props_si_incomp, props_si_multi, props_si_multi_components, props_si_verbose, props1_si,
+    sensible_heat, triple_point,
+};
 
 #[test]
 fn props_si_returns_error_for_invalid_request() {
@@ -15,6 +20,25 @@ fn props_si_returns_error_for_invalid_request() {
     );
 }
 
+#[test]
+fn props_si_verbose_keeps_value_and_message() {
+    let _guard = common::test_lock().lock().unwrap();
+    let (value, message) = props_si_verbose("Hmass", "P", 101_325.0, "T", 300.0, "Water");
+    assert_eq!(
+        value,
+        props_si("Hmass", "P", 101_325.0, "T", 300.0, "Water").unwrap()
+    );
+    assert_eq!(message, None);
+
+    let (value, message) = props_si_verbose("Hmass", "P", 101_325.0, "T", 300.0, "NotAFluid");
+    assert!(!value.is_finite());
+    assert!(message.is_some_and(|m| !m.is_empty()));
+
+    let (value, message) = props_si_verbose("Hmass", "P", 101_325.0, "T", 300.0, "Wa\0ter");
+    assert!(value.is_nan());
+    assert!(message.is_some_and(|m| m.contains("fluid")));
+}
+
 #[test]
 fn props_si_success_path() -> Result<()> {
     let _guard = common::test_lock().lock().unwrap();
@@ -31,3 +55,307 @@ fn props1_si_success_path() -> Result<()> {
     assert!(t_crit > 600.0);
     Ok(())
 }
+
+#[test]
+fn props_ksi_scales_energy_and_pressure_outputs() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let h_si = props_si("Hmass", "P", 101_325.0, "T", 300.0, "Water")?;
+    let h_ksi = props_ksi("Hmass", "P", 101_325.0, "T", 300.0, "Water")?;
+    common::assert_close(h_ksi, h_si / 1000.0, 1e-12, 1e-12, "kSI enthalpy");
+
+    let p_ksi = props_ksi("P", "T", 373.0, "Q", 0.0, "Water")?;
+    let p_si = props_si("P", "T", 373.0, "Q", 0.0, "Water")?;
+    common::assert_close(p_ksi, p_si / 1000.0, 1e-12, 1e-12, "kSI pressure");
+
+    // Aliases are scaled like the canonical name.
+    let pcrit_ksi = props_ksi("Pcrit", "T", 300.0, "P", 101_325.0, "Water")?;
+    let pcrit_si = props_si("Pcrit", "T", 300.0, "P", 101_325.0, "Water")?;
+    common::assert_close(pcrit_ksi, pcrit_si / 1000.0, 1e-12, 1e-12, "kSI alias");
+
+    let t_ksi = props_ksi("T", "P", 101_325.0, "Q", 0.0, "Water")?;
+    let t_si = props_si("T", "P", 101_325.0, "Q", 0.0, "Water")?;
+    assert_eq!(t_ksi, t_si, "temperature is not rescaled");
+
+    // Residual energies are outside the listed outputs and stay in SI.
+    let hr_ksi = props_ksi("Hmolar_residual", "P", 101_325.0, "T", 300.0, "Water")?;
+    let hr_si = props_si("Hmolar_residual", "P", 101_325.0, "T", 300.0, "Water")?;
+    assert_eq!(hr_ksi, hr_si, "residual enthalpy is not rescaled");
+    Ok(())
+}
+
+#[test]
+fn props_si_multi_matches_props_si() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let outputs = ["Hmass", "Dmass"];
+    let pressures = [101_325.0, 2.0e5, 5.0e5];
+    let temperatures = [300.0, 320.0, 350.0];
+    let table = props_si_multi(&outputs, "P", &pressures, "T", &temperatures, "Water")?;
+    assert_eq!(table.len(), outputs.len());
+    for (row, output) in table.iter().zip(outputs) {
+        assert_eq!(row.len(), pressures.len());
+        for (&value, (&p, &t)) in row.iter().zip(pressures.iter().zip(&temperatures)) {
+            let expected = props_si(output, "P", p, "T", t, "Water")?;
+            common::assert_close(value, expected, 1e-10, 1e-8, output);
+        }
+    }
+
+    assert!(props_si_multi(&outputs, "P", &pressures, "T", &temperatures[..2], "Water").is_err());
+    Ok(())
+}
+
+#[test]
+fn props_si_multi_accepts_incompressible_concentration() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let fluid = "INCOMP::MEG[0.5]";
+    let temperatures = [280.0, 300.0];
+    let pressures = [101_325.0; 2];
+    let table = props_si_multi(&["Dmass"], "T", &temperatures, "P", &pressures, fluid)?;
+    for (&value, &t) in table[0].iter().zip(&temperatures) {
+        let expected = props_si("Dmass", "T", t, "P", 101_325.0, fluid)?;
+        common::assert_close(value, expected, 1e-10, 0.0, "Dmass");
+    }
+    Ok(())
+}
+
+#[test]
+fn props_si_array_matches_props_si_and_names_failing_index() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let pressures = [101_325.0, 2.0e5, 5.0e5];
+    let temperatures = [300.0, 320.0, 350.0];
+    let h = props_si_array("Hmass", "P", &pressures, "T", &temperatures, "Water")?;
+    assert_eq!(h.len(), pressures.len());
+    for (&value, (&p, &t)) in h.iter().zip(pressures.iter().zip(&temperatures)) {
+        let expected = props_si("Hmass", "P", p, "T", t, "Water")?;
+        common::assert_close(value, expected, 1e-12, 0.0, "Hmass");
+    }
+    assert!(props_si_array("Hmass", "P", &[], "T", &[], "Water")?.is_empty());
+
+    assert!(matches!(
+        props_si_array("Hmass", "P", &pressures, "T", &temperatures[..2], "Water"),
+        Err(Error::InvalidInput(_))
+    ));
+    let err = props_si_array(
+        "Hmass",
+        "P",
+        &pressures,
+        "T",
+        &[300.0, -5.0, 350.0],
+        "Water",
+    )
+    .expect_err("negative temperature should fail");
+    assert!(
+        matches!(&err, Error::Computation { context, .. } if context.ends_with("at index 1")),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn props_si_multi_components_matches_fluid_string() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let outputs = ["Hmass", "Smass", "Dmass"];
+    let pressures = [1.0e5, 5.0e5];
+    let temperatures = [300.0, 320.0];
+    let air = MixtureSpec {
+        backend: "HEOS",
+        fluids: &["Nitrogen", "Oxygen"],
+        fractions: &[0.79, 0.21],
+    };
+    let table = props_si_multi_components(&outputs, "P", &pressures, "T", &temperatures, air)?;
+    let expected = props_si_multi(
+        &outputs,
+        "P",
+        &pressures,
+        "T",
+        &temperatures,
+        "HEOS::Nitrogen[0.79]&Oxygen[0.21]",
+    )?;
+    assert_eq!(table.len(), outputs.len());
+    assert_eq!(table, expected);
+
+    for (fluids, fractions) in [
+        (&["Nitrogen", "Oxygen"][..], &[1.0][..]),
+        (&[][..], &[][..]),
+    ] {
+        assert!(matches!(
+            props_si_multi_components(
+                &outputs,
+                "P",
+                &pressures,
+                "T",
+                &temperatures,
+                MixtureSpec {
+                    fractions,
+                    fluids,
+                    ..air
+                },
+            ),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn triple_point_of_water_and_incompressibles() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let (t, p) = triple_point("Water")?;
+    common::assert_close(t, 273.16, 1e-6, 1e-6, "water triple-point temperature");
+    common::assert_close(p, 611.655, 1e-4, 1e-3, "water triple-point pressure");
+
+    assert!(matches!(
+        triple_point("INCOMP::MEG-50%"),
+        Err(Error::InvalidInput(_))
+    ));
+    assert!(matches!(
+        triple_point("NotAFluid"),
+        Err(Error::Computation { .. })
+    ));
+    Ok(())
+}
+
+#[test]
+fn critical_compressibility_uses_a_molar_basis() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    common::assert_close(
+        critical_compressibility("Water")?,
+        0.229,
+        5e-3,
+        0.0,
+        "water Z_c",
+    );
+    common::assert_close(
+        critical_compressibility("CarbonDioxide")?,
+        0.2746,
+        5e-3,
+        0.0,
+        "carbon dioxide Z_c",
+    );
+    // Same value on a mass basis, with the specific gas constant R / M.
+    let fluid = "Nitrogen";
+    let r_specific = props1_si("gas_constant", fluid)? / props1_si("molar_mass", fluid)?;
+    let z_mass = props1_si("pcrit", fluid)?
+        / (props1_si("rhomass_critical", fluid)? * r_specific * props1_si("Tcrit", fluid)?);
+    common::assert_close(
+        critical_compressibility(fluid)?,
+        z_mass,
+        1e-9,
+        0.0,
+        "nitrogen Z_c",
+    );
+    Ok(())
+}
+
+#[test]
+fn normal_boiling_point_at_one_atmosphere() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    common::assert_close(
+        normal_boiling_point("Water")?,
+        373.124,
+        1e-5,
+        0.0,
+        "water NBP",
+    );
+    common::assert_close(
+        normal_boiling_point("Nitrogen")?,
+        77.355,
+        1e-4,
+        0.0,
+        "nitrogen NBP",
+    );
+    let err = normal_boiling_point("CarbonDioxide").expect_err("CO2 sublimes at 1 atm");
+    assert!(
+        matches!(&err, Error::InvalidInput(msg) if msg.contains("triple-point pressure")),
+        "unexpected error: {err}"
+    );
+    Ok(())
+}
+
+#[test]
+fn sensible_heat_rejects_paths_through_the_dome() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let p = 101_325.0;
+    let q = sensible_heat("Water", p, 293.15, 353.15)?;
+    let expected = props_si("Hmass", "P", p, "T", 353.15, "Water")?
+        - props_si("Hmass", "P", p, "T", 293.15, "Water")?;
+    assert_eq!(q, expected);
+    assert_eq!(sensible_heat("Water", p, 353.15, 293.15)?, -q);
+
+    let err = sensible_heat("Water", p, 350.0, 400.0).expect_err("boiling is not sensible heat");
+    assert!(
+        matches!(&err, Error::InvalidInput(msg) if msg.contains("saturation dome")),
+        "unexpected error: {err}"
+    );
+    // Above the critical pressure there is no dome to cross.
+    assert!(sensible_heat("Water", 25.0e6, 350.0, 700.0)? > 0.0);
+    Ok(())
+}
+
+#[test]
+fn props_si_incomp_rejects_temperatures_below_freezing() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let fluid = "INCOMP::MEG-50%";
+    let t_freeze = props1_si("T_freeze", fluid)?;
+
+    let err = props_si_incomp("Dmass", t_freeze - 5.0, 101_325.0, fluid)
+        .expect_err("temperatures below the freeze point should be rejected");
+    assert!(
+        matches!(&err, Error::InvalidInput(msg) if msg.starts_with("temperature below freeze point")
+            && msg.contains(&t_freeze.to_string())),
+        "unexpected error: {err}"
+    );
+
+    let t = t_freeze + 40.0;
+    assert_eq!(
+        props_si_incomp("Dmass", t, 101_325.0, fluid)?,
+        props_si("Dmass", "T", t, "P", 101_325.0, fluid)?
+    );
+    Ok(())
+}
+
+#[test]
+fn enthalpy_at_pq_traces_the_two_phase_dome() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let p = 500_000.0;
+    let h_liq = enthalpy_at_pq("R134a", p, 0.0)?;
+    let h_vap = enthalpy_at_pq("R134a", p, 1.0)?;
+    assert!(h_vap > h_liq);
+    common::assert_close(
+        enthalpy_at_pq("R134a", p, 0.25)?,
+        h_liq + 0.25 * (h_vap - h_liq),
+        1e-9,
+        1e-6,
+        "quality-weighted enthalpy",
+    );
+    assert_eq!(h_liq, props_si("Hmass", "P", p, "Q", 0.0, "R134a")?);
+    for q in [-0.1, 1.1, f64::NAN] {
+        assert!(matches!(
+            enthalpy_at_pq("R134a", p, q),
+            Err(Error::InvalidInput(_))
+        ));
+    }
+    Ok(())
+}
+
+#[test]
+fn props_si_derivative_matches_hand_written_expression() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let cp = Derivative {
+        of: Param::Hmass,
+        wrt: Param::T,
+        at_constant: Param::P,
+    };
+    let value = props_si_derivative(&cp, "P", 101_325.0, "T", 300.0, "Nitrogen")?;
+    assert_eq!(
+        value,
+        props_si("d(Hmass)/d(T)|P", "P", 101_325.0, "T", 300.0, "Nitrogen")?
+    );
+    common::assert_close(
+        value,
+        props_si("Cpmass", "P", 101_325.0, "T", 300.0, "Nitrogen")?,
+        1e-9,
+        1e-9,
+        "(dh/dT)_P equals cp",
+    );
+    Ok(())
+}
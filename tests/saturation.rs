@@ -0,0 +1,69 @@
+#[path = "common/mod.rs"]
+mod common;
+
+use anyhow::Result;
+use coolprop::{AbstractState, SaturationCurve, SaturationTable, props_si};
+
+#[test]
+fn saturation_curve_is_strictly_monotonic() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    let curve = SaturationCurve::build(&mut state, 200)?;
+
+    let temperatures = curve.temperatures();
+    let pressures = curve.pressures_ascending();
+    assert!(temperatures.len() > 150, "too many points were dropped");
+    assert_eq!(temperatures.len(), pressures.len());
+    assert_eq!(temperatures.len(), curve.rhomolar_liq().len());
+    assert_eq!(temperatures.len(), curve.rhomolar_vap().len());
+    assert!(temperatures.windows(2).all(|w| w[0] < w[1]));
+    assert!(pressures.windows(2).all(|w| w[0] < w[1]));
+
+    let t_mid = temperatures[temperatures.len() / 2];
+    let p_mid = pressures[temperatures.len() / 2];
+    common::assert_close(
+        p_mid,
+        props_si("P", "T", t_mid, "Q", 0.0, "Water")?,
+        1e-8,
+        1e-6,
+        "mid-curve saturation pressure",
+    );
+    Ok(())
+}
+
+#[test]
+fn saturation_curve_rejects_too_few_points() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    assert!(SaturationCurve::build(&mut state, 1).is_err());
+    Ok(())
+}
+
+#[test]
+fn saturation_table_interpolates_between_rows() -> Result<()> {
+    let _guard = common::test_lock().lock().unwrap();
+    let table = SaturationTable::build("Water", 300.0, 400.0, 101)?;
+    assert_eq!(table.rows().len(), 101);
+
+    let row = table
+        .interpolate_at_temperature(350.5)
+        .expect("350.5 K is inside the table");
+    common::assert_close(
+        row.pressure,
+        props_si("P", "T", 350.5, "Q", 0.0, "Water")?,
+        1e-3,
+        0.0,
+        "interpolated saturation pressure",
+    );
+    common::assert_close(
+        row.h_vap - row.h_liq,
+        props_si("Hmass", "T", 350.5, "Q", 1.0, "Water")?
+            - props_si("Hmass", "T", 350.5, "Q", 0.0, "Water")?,
+        1e-4,
+        0.0,
+        "interpolated latent heat",
+    );
+    assert!(table.interpolate_at_temperature(401.0).is_none());
+    assert!(SaturationTable::build("Water", 400.0, 300.0, 10).is_err());
+    Ok(())
+}
@@ -0,0 +1,48 @@
+#![cfg(feature = "serde")]
+
+#[path = "common/mod.rs"]
+mod common;
+
+use anyhow::Result;
+use common::test_lock;
+use coolprop::{AbstractState, BatchCommonOutputs, CriticalPoint, InputPair, PhaseEnvelope};
+
+#[test]
+fn phase_envelope_round_trips_through_json() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "R32&R125")?;
+    state.set_fractions(&[0.5, 0.5])?;
+    state.build_phase_envelope("none")?;
+    let envelope = state.phase_envelope()?;
+    assert!(!envelope.temperature.is_empty());
+
+    let json = serde_json::to_string(&envelope)?;
+    let value: serde_json::Value = serde_json::from_str(&json)?;
+    assert!(
+        value["x"][0].is_array(),
+        "x should serialize as nested arrays"
+    );
+    let restored: PhaseEnvelope = serde_json::from_str(&json)?;
+    assert_eq!(restored, envelope);
+    Ok(())
+}
+
+#[test]
+fn batch_outputs_and_critical_points_round_trip_through_json() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let mut state = AbstractState::new("HEOS", "Water")?;
+    let outputs = state.update_and_common_out(InputPair::PT, &[1e5, 2e5], &[300.0, 310.0])?;
+    let restored: BatchCommonOutputs = serde_json::from_str(&serde_json::to_string(&outputs)?)?;
+    assert_eq!(restored, outputs);
+
+    let point = CriticalPoint {
+        temperature: 647.096,
+        pressure: 22.064e6,
+        rhomolar: 17_873.7,
+        stable: true,
+    };
+    let json = serde_json::to_string(&point)?;
+    assert!(json.contains("\"temperature\"") && json.contains("\"stable\""));
+    assert_eq!(serde_json::from_str::<CriticalPoint>(&json)?, point);
+    Ok(())
+}
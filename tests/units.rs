@@ -0,0 +1,51 @@
+#![cfg(feature = "uom")]
+
+#[path = "common/mod.rs"]
+mod common;
+
+use anyhow::Result;
+use common::{assert_close, test_lock};
+use coolprop::{SiQuantity, props_si, props_si_uom};
+use uom::si::f64::{AvailableEnergy, MassDensity, Pressure, ThermodynamicTemperature};
+use uom::si::{
+    available_energy::kilojoule_per_kilogram, mass_density::kilogram_per_cubic_meter,
+    pressure::kilopascal, thermodynamic_temperature::degree_celsius,
+};
+
+#[test]
+fn uom_quantities_round_trip_through_props_si() -> Result<()> {
+    let _guard = test_lock().lock().unwrap();
+    let p = Pressure::new::<kilopascal>(101.325);
+    let t = ThermodynamicTemperature::new::<degree_celsius>(25.0);
+
+    let rho: MassDensity = props_si_uom("Dmass", "P", p, "T", t, "Water")?;
+    let expected = props_si("Dmass", "P", 101_325.0, "T", 298.15, "Water")?;
+    assert_close(
+        rho.get::<kilogram_per_cubic_meter>(),
+        expected,
+        1e-12,
+        0.0,
+        "Dmass from uom inputs",
+    );
+
+    let h: AvailableEnergy = props_si_uom("Hmass", "P", p, "T", t, "Water")?;
+    let expected = props_si("Hmass", "P", 101_325.0, "T", 298.15, "Water")?;
+    assert_close(
+        h.get::<kilojoule_per_kilogram>(),
+        expected / 1e3,
+        1e-12,
+        0.0,
+        "Hmass from uom inputs",
+    );
+
+    // Bare f64 inputs are taken as SI and can be mixed with quantities.
+    let t_back: ThermodynamicTemperature = props_si_uom("T", "P", p, "Hmass", h.to_si(), "Water")?;
+    assert_close(
+        t_back.get::<degree_celsius>(),
+        25.0,
+        1e-9,
+        1e-9,
+        "T from P/H round trip",
+    );
+    Ok(())
+}